@@ -0,0 +1,99 @@
+use crate::maze::{Coords, Direction, LinkType, Maze};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+///Removes `fraction` (0.0-1.0) of the dead ends by carving one extra wall in each,
+///turning the perfect maze into one with loops. Walls towards other dead ends are preferred,
+///so a single carve can fix two dead ends. Returns the number of walls carved.
+pub fn braid(maze: &mut Maze, fraction: f64, rng: &mut dyn RngCore) -> usize {
+    let mut dead_ends = get_dead_ends(maze);
+    dead_ends.shuffle(rng);
+    let count = (dead_ends.len() as f64 * fraction.clamp(0.0, 1.0)).round() as usize;
+
+    let mut carved = 0;
+    for coords in dead_ends.into_iter().take(count) {
+        if !is_dead_end(maze, &coords) {
+            continue;
+        }
+
+        let walls: Vec<Direction> = maze
+            .get_directions_possible(&coords)
+            .into_iter()
+            .filter(|direction| maze.borrow_cell(&coords).get_link(direction) == &LinkType::Wall)
+            .collect();
+        let preferred: Vec<Direction> = walls
+            .iter()
+            .filter(|direction| is_dead_end(maze, &maze.get_neighbour(&coords, direction)))
+            .cloned()
+            .collect();
+
+        let candidates = if preferred.is_empty() {
+            walls
+        } else {
+            preferred
+        };
+        if let Some(direction) = candidates.choose(rng) {
+            maze.carve_path(&coords, direction);
+            carved += 1;
+        }
+    }
+    carved
+}
+
+pub fn is_dead_end(maze: &Maze, coords: &Coords) -> bool {
+    maze.get_possible_moves(coords).len() == 1
+}
+
+pub fn get_dead_ends(maze: &Maze) -> Vec<Coords> {
+    let (width, height) = maze.get_dimensions();
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| Coords { x, y }))
+        .filter(|coords| is_dead_end(maze, coords))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_braid {
+    use super::get_dead_ends;
+    use crate::maze::MazeBuilder;
+    use crate::maze_generator::recursive_backtracker::RecursiveBacktrackerGenerator;
+    use crate::solver::solve_maze;
+
+    #[test]
+    pub fn test_full_braid_removes_every_dead_end() {
+        let maze = MazeBuilder::from_generator(Box::new(RecursiveBacktrackerGenerator))
+            .with_seed(3)
+            .with_braid(1.0)
+            .generate(20, 20)
+            .unwrap();
+        assert!(get_dead_ends(&maze).is_empty());
+        assert!(maze.count_passages() > 20 * 20 - 1);
+        assert!(solve_maze(&maze).is_ok());
+    }
+
+    #[test]
+    pub fn test_zero_braid_keeps_perfect_maze() {
+        let maze = MazeBuilder::from_generator(Box::new(RecursiveBacktrackerGenerator))
+            .with_braid(0.0)
+            .generate(20, 20)
+            .unwrap();
+        assert_eq!(maze.count_passages(), 20 * 20 - 1);
+    }
+
+    #[test]
+    pub fn test_partial_braid_keeps_some_dead_ends() {
+        let perfect = MazeBuilder::from_generator(Box::new(RecursiveBacktrackerGenerator))
+            .with_seed(11)
+            .generate(30, 30)
+            .unwrap();
+        let braided = MazeBuilder::from_generator(Box::new(RecursiveBacktrackerGenerator))
+            .with_seed(11)
+            .with_braid(0.5)
+            .generate(30, 30)
+            .unwrap();
+        let before = get_dead_ends(&perfect).len();
+        let after = get_dead_ends(&braided).len();
+        assert!(after < before);
+        assert!(after > 0);
+    }
+}
@@ -0,0 +1,79 @@
+///Union-find over the indices `0..size`, with path compression and union by rank
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    set_count: usize,
+}
+
+impl DisjointSet {
+    pub fn new(size: usize) -> DisjointSet {
+        DisjointSet {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            set_count: size,
+        }
+    }
+
+    ///Representative of the set containing `index`
+    pub fn find(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = index;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    ///Merges the sets of `a` and `b`, returns false if they were already the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        if root_a == root_b {
+            return false;
+        }
+
+        match self.rank[root_a].cmp(&self.rank[root_b]) {
+            std::cmp::Ordering::Less => self.parent[root_a] = root_b,
+            std::cmp::Ordering::Greater => self.parent[root_b] = root_a,
+            std::cmp::Ordering::Equal => {
+                self.parent[root_b] = root_a;
+                self.rank[root_a] += 1;
+            }
+        }
+        self.set_count -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+}
+
+#[cfg(test)]
+mod tests_disjoint_set {
+    use super::DisjointSet;
+
+    #[test]
+    pub fn test_union_and_find() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.set_count(), 6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+        assert!(set.same_set(0, 3));
+        assert!(!set.same_set(0, 4));
+        assert_eq!(set.set_count(), 3);
+    }
+}
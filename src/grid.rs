@@ -0,0 +1,74 @@
+use crate::maze_generator::random_index;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::io;
+
+///Topology independent view of a maze: which cells exist, which of them are adjacent
+///and which of those are connected by a passage. Generators and solvers written against
+///this trait work on square, hexagonal and every other grid shape.
+pub trait Grid {
+    type Node: Copy + Eq + Hash + Debug;
+
+    ///Every cell taking part in the maze, always in the same order
+    fn nodes(&self) -> Vec<Self::Node>;
+
+    ///Adjacent cells, whether there is a wall in between or not
+    fn neighbours(&self, node: &Self::Node) -> Vec<Self::Node>;
+
+    ///Adjacent cells reachable through a passage
+    fn links(&self, node: &Self::Node) -> Vec<Self::Node>;
+
+    ///Removes the wall between two adjacent cells
+    fn link(&mut self, a: &Self::Node, b: &Self::Node);
+
+    ///True if `node` is one of [`Grid::nodes`], grids that can tell without listing all nodes
+    ///should override it
+    fn contains(&self, node: &Self::Node) -> bool {
+        self.nodes().contains(node)
+    }
+
+    fn is_in_maze(&self, node: &Self::Node) -> bool;
+
+    fn set_in_maze(&mut self, node: &Self::Node, in_maze: bool);
+
+    fn start(&self) -> Self::Node;
+
+    fn end(&self) -> Self::Node;
+
+    ///Every exit of the maze, [`Grid::end`] included
+    fn ends(&self) -> Vec<Self::Node> {
+        vec![self.end()]
+    }
+
+    ///Uniformly random node, grids that can pick one without listing all nodes should override it
+    fn random_node(&self, rng: &mut dyn RngCore) -> Self::Node {
+        let nodes = self.nodes();
+        nodes[random_index(rng, nodes.len())]
+    }
+
+    ///Neighbours that are not part of the maze yet
+    fn unvisited_neighbours(&self, node: &Self::Node) -> Vec<Self::Node> {
+        self.neighbours(node)
+            .into_iter()
+            .filter(|neighbour| !self.is_in_maze(neighbour))
+            .collect()
+    }
+}
+
+///Generator that only relies on [`Grid`], so it can run on any topology
+pub trait GridGenerator {
+    ///All randomness has to come from `rng`, so that a seed reproduces the same maze
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error>;
+}
+
+///Runs `generator` on `grid` with the same seeded RNG [`crate::maze::MazeBuilder`] uses
+pub fn generate_with_seed<G: Grid, T: GridGenerator>(
+    generator: &T,
+    grid: &mut G,
+    seed: u64,
+) -> Result<(), io::Error> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    generator.generate_grid(grid, &mut rng)
+}
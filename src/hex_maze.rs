@@ -0,0 +1,237 @@
+use crate::grid::Grid;
+use crate::maze::{Coords, LinkType};
+use serde::{Deserialize, Serialize};
+
+///Sides of a flat-topped hexagon, clockwise starting at the top
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::North,
+        HexDirection::NorthEast,
+        HexDirection::SouthEast,
+        HexDirection::South,
+        HexDirection::SouthWest,
+        HexDirection::NorthWest,
+    ];
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn opposite(&self) -> HexDirection {
+        HexDirection::ALL[(self.index() + 3) % 6]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HexCell {
+    ///Link on each side, indexed by [`HexDirection::index`]
+    pub links: [LinkType; 6],
+    pub in_maze: bool,
+}
+
+///Maze of flat-topped hexagons with six neighbours per cell.
+///Cells are stored in columns, every odd column is shifted down by half a cell.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HexMaze {
+    data: Vec<Vec<HexCell>>,
+    width: usize,
+    height: usize,
+    start_point: Coords,
+    end_point: Coords,
+    seed: u64,
+}
+
+impl HexMaze {
+    ///Hex grid with every wall standing, ready for a [`crate::grid::GridGenerator`]
+    pub fn new(width: usize, height: usize, seed: u64) -> HexMaze {
+        let base_cell = HexCell {
+            links: [
+                LinkType::Wall,
+                LinkType::Wall,
+                LinkType::Wall,
+                LinkType::Wall,
+                LinkType::Wall,
+                LinkType::Wall,
+            ],
+            in_maze: false,
+        };
+
+        HexMaze {
+            data: vec![vec![base_cell; width]; height],
+            width,
+            height,
+            start_point: Coords { x: 0, y: 0 },
+            end_point: Coords {
+                x: width - 1,
+                y: height - 1,
+            },
+            seed,
+        }
+    }
+
+    pub fn borrow_cell(&self, coords: &Coords) -> &HexCell {
+        &self.data[coords.y][coords.x]
+    }
+
+    pub fn borrow_cell_mut(&mut self, coords: &Coords) -> &mut HexCell {
+        &mut self.data[coords.y][coords.x]
+    }
+
+    ///Neighbour in `direction`, None at the border of the grid
+    pub fn get_neighbour(&self, coords: &Coords, direction: &HexDirection) -> Option<Coords> {
+        let (x, y) = (coords.x as isize, coords.y as isize);
+        //odd columns sit half a cell lower, so their diagonal neighbours are one row further down
+        let shift = (coords.x % 2) as isize;
+        let (nx, ny) = match direction {
+            HexDirection::North => (x, y - 1),
+            HexDirection::South => (x, y + 1),
+            HexDirection::NorthEast => (x + 1, y - 1 + shift),
+            HexDirection::SouthEast => (x + 1, y + shift),
+            HexDirection::SouthWest => (x - 1, y + shift),
+            HexDirection::NorthWest => (x - 1, y - 1 + shift),
+        };
+
+        if nx < 0 || ny < 0 || nx >= self.width as isize || ny >= self.height as isize {
+            return None;
+        }
+        Some(Coords {
+            x: nx as usize,
+            y: ny as usize,
+        })
+    }
+
+    pub fn get_directions_possible(&self, coords: &Coords) -> Vec<HexDirection> {
+        HexDirection::ALL
+            .into_iter()
+            .filter(|direction| self.get_neighbour(coords, direction).is_some())
+            .collect()
+    }
+
+    pub fn direction_to(&self, from: &Coords, to: &Coords) -> Option<HexDirection> {
+        HexDirection::ALL
+            .into_iter()
+            .find(|direction| self.get_neighbour(from, direction) == Some(*to))
+    }
+
+    pub fn get_dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_start_point(&self) -> &Coords {
+        &self.start_point
+    }
+
+    pub fn get_end_point(&self) -> &Coords {
+        &self.end_point
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Grid for HexMaze {
+    type Node = Coords;
+
+    fn nodes(&self) -> Vec<Coords> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Coords { x, y }))
+            .collect()
+    }
+
+    fn neighbours(&self, coords: &Coords) -> Vec<Coords> {
+        HexDirection::ALL
+            .iter()
+            .filter_map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn links(&self, coords: &Coords) -> Vec<Coords> {
+        let cell = self.borrow_cell(coords);
+        HexDirection::ALL
+            .iter()
+            .filter(|direction| cell.links[direction.index()] == LinkType::Path)
+            .filter_map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn link(&mut self, a: &Coords, b: &Coords) {
+        if let Some(direction) = self.direction_to(a, b) {
+            self.borrow_cell_mut(a).links[direction.index()] = LinkType::Path;
+            self.borrow_cell_mut(b).links[direction.opposite().index()] = LinkType::Path;
+        }
+    }
+
+    fn is_in_maze(&self, coords: &Coords) -> bool {
+        self.borrow_cell(coords).in_maze
+    }
+
+    fn set_in_maze(&mut self, coords: &Coords, in_maze: bool) {
+        self.borrow_cell_mut(coords).in_maze = in_maze;
+    }
+
+    fn start(&self) -> Coords {
+        self.start_point
+    }
+
+    fn end(&self) -> Coords {
+        self.end_point
+    }
+}
+
+#[cfg(test)]
+mod tests_hex_maze {
+    use super::{HexDirection, HexMaze};
+    use crate::grid::{generate_with_seed, Grid};
+    use crate::maze::Coords;
+    use crate::maze_generator::wilson::WilsonGenerator;
+    use crate::solver::solve_grid;
+
+    #[test]
+    pub fn test_neighbours_are_symmetric() {
+        let maze = HexMaze::new(7, 5, 0);
+        for coords in maze.nodes() {
+            for direction in maze.get_directions_possible(&coords) {
+                let neighbour = maze.get_neighbour(&coords, &direction).unwrap();
+                assert_eq!(
+                    maze.get_neighbour(&neighbour, &direction.opposite()),
+                    Some(coords)
+                );
+            }
+        }
+        assert_eq!(maze.neighbours(&Coords { x: 3, y: 2 }).len(), 6);
+        assert_eq!(
+            maze.get_neighbour(&Coords { x: 1, y: 0 }, &HexDirection::NorthEast),
+            Some(Coords { x: 2, y: 0 })
+        );
+    }
+
+    #[test]
+    pub fn test_wilson_generates_perfect_hex_maze() {
+        let mut maze = HexMaze::new(12, 9, 7);
+        generate_with_seed(&WilsonGenerator, &mut maze, 7).unwrap();
+        let link_count: usize = maze.nodes().iter().map(|c| maze.links(c).len()).sum();
+        assert_eq!(link_count / 2, 12 * 9 - 1);
+        let path = solve_grid(&maze, &maze.start(), &maze.end()).unwrap();
+        assert_eq!(path.first(), Some(maze.get_start_point()));
+        assert_eq!(path.last(), Some(maze.get_end_point()));
+    }
+}
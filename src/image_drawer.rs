@@ -15,6 +15,17 @@ pub trait GenericImageExt {
     );
 
     fn draw_full_square_with_center(&mut self, center: &Coords, radius: u32, color: Self::Pixel);
+
+    ///Arc around `center`, angles are in radians and go clockwise from the east
+    fn draw_arc_with_thickness(
+        &mut self,
+        center: &Coords,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        thickness: u32,
+        color: Self::Pixel,
+    );
 }
 
 impl<T> GenericImageExt for T
@@ -65,6 +76,10 @@ where
         thickness: u32,
         color: Self::Pixel,
     ) {
+        //a one pixel wide line needs no squares around its points
+        if thickness <= 1 {
+            return self.draw_line(start, end, color);
+        }
         if !self.in_bounds(start.x as u32, start.y as u32)
             || !self.in_bounds(end.x as u32, end.y as u32)
         {
@@ -105,7 +120,11 @@ where
 
     fn draw_full_square_with_center(&mut self, center: &Coords, radius: u32, color: Self::Pixel) {
         let r_pos = radius as i32 / 2;
-        let r_neg = if radius % 2 == 0 { -r_pos + 1 } else { -r_pos };
+        let r_neg = if radius.is_multiple_of(2) {
+            -r_pos + 1
+        } else {
+            -r_pos
+        };
 
         let x = center.x as i32;
         let y = center.y as i32;
@@ -126,6 +145,32 @@ where
             }
         }
     }
+
+    fn draw_arc_with_thickness(
+        &mut self,
+        center: &Coords,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        thickness: u32,
+        color: Self::Pixel,
+    ) {
+        //one step per pixel of arc length, so the squares overlap without gaps
+        let steps = ((end_angle - start_angle).abs() * radius).ceil().max(1.0) as u32;
+        for step in 0..=steps {
+            let angle = start_angle + (end_angle - start_angle) * step as f32 / steps as f32;
+            let x = center.x as f32 + radius * angle.cos();
+            let y = center.y as f32 + radius * angle.sin();
+            if x < 0.0 || y < 0.0 {
+                continue;
+            }
+            let coords = Coords {
+                x: x.round() as usize,
+                y: y.round() as usize,
+            };
+            self.draw_full_square_with_center(&coords, thickness, color);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -136,10 +181,39 @@ mod tests_image_drawers {
 
     #[test]
     pub fn test_draw_rectangle() {
-        let mut image = RgbImage::from_pixel(10, 10, Rgb { 0: [0x00; 3] });
+        let mut image = RgbImage::from_pixel(10, 10, Rgb([0x00; 3]));
         let begin = Coords { x: 2, y: 2 };
         let end = Coords { x: 2, y: 5 };
         image.draw_line_with_thickness(&begin, &end, 3, Rgb([0xff; 3]));
         image.save("temp_out.png").unwrap();
     }
+
+    #[test]
+    pub fn test_draw_line() {
+        let mut image = RgbImage::from_pixel(10, 10, Rgb([0x00; 3]));
+        image.draw_line(
+            &Coords { x: 1, y: 2 },
+            &Coords { x: 8, y: 6 },
+            Rgb([0xff; 3]),
+        );
+        let lit = image
+            .pixels()
+            .filter(|pixel| **pixel == Rgb([0xff; 3]))
+            .count();
+        //one pixel per column along the longer axis
+        assert_eq!(lit, 8);
+        assert_eq!(image.get_pixel(1, 2), &Rgb([0xff; 3]));
+        assert_eq!(image.get_pixel(8, 6), &Rgb([0xff; 3]));
+    }
+
+    #[test]
+    pub fn test_draw_arc() {
+        let mut image = RgbImage::from_pixel(21, 21, Rgb([0x00; 3]));
+        let center = Coords { x: 10, y: 10 };
+        image.draw_arc_with_thickness(&center, 8.0, 0.0, std::f32::consts::PI, 1, Rgb([0xff; 3]));
+        assert_eq!(image.get_pixel(18, 10), &Rgb([0xff; 3]));
+        assert_eq!(image.get_pixel(10, 18), &Rgb([0xff; 3]));
+        assert_eq!(image.get_pixel(2, 10), &Rgb([0xff; 3]));
+        assert_eq!(image.get_pixel(10, 2), &Rgb([0x00; 3]));
+    }
 }
@@ -1,21 +1,58 @@
-use clap::{ArgGroup, Parser};
-use maze::{MazeBuilder, MazeGenerator};
+use clap::{Parser, ValueEnum};
+use grid::{generate_with_seed, Grid};
+use hex_maze::HexMaze;
+use mask::Mask;
+use maze::{Coords, Maze, MazeBuilder, MazeGenerator, Placement, Wrap};
+use maze_3d::Maze3D;
+use maze_generator::aldous_broder::{AldousBroderGenerator, AldousBroderWilsonGenerator};
+use maze_generator::binary_tree::BinaryTreeGenerator;
+use maze_generator::eller::EllerGenerator;
+use maze_generator::growing_tree::{GrowingTreeGenerator, SelectorSpec};
+use maze_generator::hunt_and_kill::HuntAndKillGenerator;
+use maze_generator::kruskal::KruskalGenerator;
+use maze_generator::prim::PrimGenerator;
+use maze_generator::recursive_backtracker::RecursiveBacktrackerGenerator;
+use maze_generator::recursive_division::RecursiveDivisionGenerator;
+use maze_generator::sidewinder::SidewinderGenerator;
+use maze_generator::weave::WeaveGenerator;
 use maze_generator::wilson::WilsonGenerator;
+use maze_generator::Bias;
 use maze_image_builder::ConfigArray;
+use maze_solver::dead_end_filling::DeadEndFillingSolver;
+use maze_solver::random_mouse::RandomMouseSolver;
+use maze_solver::tremaux::TremauxSolver;
+use maze_solver::wall_follower::{Hand, WallFollowerSolver};
+use maze_solver::Solver;
+use polar_maze::PolarMaze;
 use rand::distributions::{Distribution, Standard};
-use rand::Rng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use room::Room;
+use serde::Serialize;
 use std::fs;
 use std::process::exit;
+use triangle_maze::TriangleMaze;
 
+pub mod braid;
+pub mod disjoint_set;
+pub mod grid;
+pub mod hex_maze;
 mod image_drawer;
-mod maze;
-mod maze_generator;
+pub mod mask;
+pub mod maze;
+pub mod maze_3d;
+pub mod maze_generator;
 mod maze_image_builder;
-mod solver;
+pub mod maze_solver;
+pub mod polar_maze;
+pub mod puzzle;
+pub mod room;
+pub mod solver;
+pub mod triangle_maze;
 
 #[derive(Parser, Debug)]
 #[command(name = "Maze Generator")]
-#[command(group(ArgGroup::new("generator").required(false).args(&["wilson", "other"])))]
 pub struct CommandArgs {
     ///Width of maze
     pub width: usize,
@@ -23,6 +60,10 @@ pub struct CommandArgs {
     ///height of maze
     pub height: usize,
 
+    ///Number of floors, square mazes with more than one floor get stairs between them
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+    pub depth: u64,
+
     ///path of output png
     #[arg(long, default_value = "./out.png")]
     pub path_out: String,
@@ -43,59 +84,242 @@ pub struct CommandArgs {
     #[arg(long, action)]
     pub solve: bool,
 
+    ///Print how many cells the breadth-first search and A* with each heuristic expand to solve the maze
+    #[arg(long, action)]
+    pub compare_solvers: bool,
+
+    ///Write the distance in moves of every cell from --distance-from into <DISTANCE_MAP>.json
+    #[arg(long)]
+    pub distance_map: Option<String>,
+
+    ///Source cell of --distance-map as x,y, the start of the maze by default
+    #[arg(long)]
+    pub distance_from: Option<Coords>,
+
+    ///Write every step the --strategy solver takes into <TRACE>.json
+    #[arg(long)]
+    pub trace: Option<String>,
+
+    ///Solving strategy recorded by --trace
+    #[arg(long, value_enum, default_value = "tremaux")]
+    pub strategy: SolverType,
+
+    ///Steps after which the random-mouse strategy gives up, 100 per cell by default
+    #[arg(long)]
+    pub max_steps: Option<usize>,
+
+    ///Print how many routes without a cell visited twice lead from the start to each exit,
+    ///and their lengths, stopping after <COUNT_PATHS> routes per exit
+    #[arg(long)]
+    pub count_paths: Option<usize>,
+
     ///Serialize maze struct into <SERIALIZE>.json and <SERIALIZE>_path.json
     #[arg(long)]
     pub serialize: Option<String>,
 
+    ///Shape of the cells, eller, binary-tree, sidewinder and recursive-division only build square mazes
+    #[arg(long, value_enum, default_value = "square")]
+    pub topology: Topology,
+
+    ///Generation algorithm, picked at random if not set
+    #[arg(long, value_enum)]
+    pub algorithm: Option<GeneratorType>,
+
+    ///Corner the binary-tree and sidewinder algorithms are biased towards
+    #[arg(long, value_enum, default_value = "north-west")]
+    pub bias: Bias,
+
+    ///Cell selection of the growing-tree algorithm: newest, oldest, random or a weighted mix like newest:75,random:25
+    #[arg(long, default_value = "newest")]
+    pub selector: SelectorSpec,
+
+    ///Seed for the random generation, the same seed and options always give the same maze.
+    ///Without --algorithm the algorithm is drawn from the seed too
     #[arg(long)]
-    wilson: bool,
+    pub seed: Option<u64>,
+
+    ///Fraction (0.0-1.0) of dead ends to remove, creating loops in the maze
+    #[arg(long, value_parser = parse_fraction)]
+    pub braid: Option<f64>,
 
+    ///Black and white PNG, only cells under white pixels are part of the maze
     #[arg(long)]
-    other: bool,
+    pub mask: Option<String>,
+
+    ///Join opposite edges of a square maze, passages can leave on one side and come back on the other
+    #[arg(long, value_enum, default_value = "none")]
+    pub wrap: Wrap,
+
+    ///Fraction of cells (0.0-1.0) after which the hybrid generator switches to Wilson
+    #[arg(long, default_value = "0.3", value_parser = parse_fraction)]
+    pub switch_fraction: f64,
+
+    ///Start cell as x,y, or auto to place it as far as possible from the end
+    #[arg(long)]
+    pub start: Option<Placement>,
+
+    ///End cell as x,y, or auto to place it as far as possible from the start.
+    ///With --start auto too, the two cells farthest apart are used. Distances are measured
+    ///before --braid and --room add loops, so on such mazes auto is only approximate
+    #[arg(long)]
+    pub end: Option<Placement>,
+
+    ///Additional exit as x,y, can be repeated
+    #[arg(long)]
+    pub exit: Vec<Coords>,
+
+    ///Number of locked doors placed on the way to the exit, each with a key that can be reached first
+    #[arg(long, default_value = "0")]
+    pub locks: usize,
+
+    ///Open room as x,y,width,height with an optional ,doors (1 by default), can be repeated
+    #[arg(long)]
+    pub room: Vec<Room>,
+
+    ///Chance (0.0-1.0) for a cell of the weave generator to become an over/under crossing
+    #[arg(long, default_value = "0.5", value_parser = parse_fraction)]
+    pub crossing_probability: f64,
 }
 
 impl CommandArgs {
-    pub fn get_generator_type(&self) -> GeneratorType {
-        if self.wilson {
-            GeneratorType::Wilson
-        } else if self.other {
-            GeneratorType::Other
-        } else {
-            rand::random()
+    ///Algorithm to generate with, a random one is drawn from `seed` so the seed alone
+    ///reproduces the maze
+    pub fn get_generator_type(&self, seed: u64) -> GeneratorType {
+        let masked = self.mask.is_some() || !self.room.is_empty();
+        match (self.algorithm, masked, self.wrap) {
+            (Some(algorithm), _, _) => algorithm,
+            //not every algorithm supports masks, rooms or wrapping, Wilson does
+            (None, true, _) | (None, false, Wrap::Cylinder | Wrap::Torus) => GeneratorType::Wilson,
+            (None, false, Wrap::None) => ChaCha8Rng::seed_from_u64(seed).gen(),
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Topology {
+    Square,
+    Hex,
+    ///Concentric rings, <HEIGHT> is the number of rings and <WIDTH> is ignored
+    Polar,
+    ///Alternating upward and downward triangles
+    Triangle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
 pub enum GeneratorType {
     Wilson,
-    Other,
+    #[value(alias = "other")]
+    RecursiveBacktracker,
+    Kruskal,
+    Prim,
+    Eller,
+    AldousBroder,
+    ///Aldous-Broder until --switch-fraction of the cells are in maze, then Wilson
+    #[value(name = "hybrid")]
+    AldousBroderWilson,
+    HuntAndKill,
+    BinaryTree,
+    Sidewinder,
+    GrowingTree,
+    RecursiveDivision,
+    ///Kruskal with passages tunnelling under each other, see --crossing-probability
+    Weave,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SolverType {
+    WallFollowerLeft,
+    WallFollowerRight,
+    Tremaux,
+    DeadEndFilling,
+    RandomMouse,
 }
 
 impl Distribution<GeneratorType> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GeneratorType {
-        match rng.gen_range(0..2) {
-            0 => GeneratorType::Wilson,
-            1 => GeneratorType::Other,
-            _ => panic!("Error during random generation"),
-        }
+        *GeneratorType::value_variants()
+            .choose(rng)
+            .expect("Error during random generation")
     }
 }
 
 pub fn main_run() {
     let config = CommandArgs::parse();
-    let g_type = config.get_generator_type();
-    let generator = get_maze_generator(g_type);
-    let maze = MazeBuilder::from_generator(generator)
-        .generate(config.width, config.height)
-        .unwrap();
+    match config.topology {
+        Topology::Square if config.depth > 1 => run_3d(config),
+        _ if config.depth > 1 => {
+            eprintln!("--depth is only available for square mazes");
+            exit(1);
+        }
+        Topology::Square => run_square(config),
+        Topology::Hex => run_hex(config),
+        Topology::Polar => run_polar(config),
+        Topology::Triangle => run_triangle(config),
+    }
+}
+
+fn run_square(config: CommandArgs) {
+    let solves = config.compare_solvers
+        || config.count_paths.is_some()
+        || config.distance_map.is_some()
+        || config.trace.is_some();
+    if config.locks > 0 && solves {
+        eprintln!(
+            "--compare-solvers, --count-paths, --distance-map and --trace ignore locked doors and can't be used with --locks"
+        );
+        exit(1);
+    }
+    let maze = build_square_maze(&config);
+    let placed = maze.get_locks().len();
+    if placed < config.locks {
+        eprintln!(
+            "Only {placed} of {} locks were placed, the route to the exit is too short or the limit of {} was reached",
+            config.locks,
+            puzzle::MAX_LOCKS
+        );
+    }
 
-    if let Some(path_str) = config.serialize {
-        let serialized = serde_json::to_string(&maze).unwrap();
-        fs::write(format!("{path_str}.json",), serialized).expect("Failed to write file");
+    if let Some(path_str) = &config.serialize {
+        let path = if maze.get_locks().is_empty() {
+            solver::solve_maze(&maze).unwrap()
+        } else {
+            solver::solve_with_keys(&maze).unwrap()
+        };
+        serialize_maze(path_str, &maze, &path);
+    }
 
-        let path = solver::solve_maze(&maze).unwrap();
-        let serialized = serde_json::to_string(&path).unwrap();
-        fs::write(format!("{path_str}_path.json"), serialized).expect("Failed to write to file");
+    if config.compare_solvers {
+        print_solver_comparison(&maze);
+    }
+
+    if let Some(limit) = config.count_paths {
+        print_path_count(&maze, limit);
+    }
+
+    if let Some(path_str) = &config.distance_map {
+        let source = config.distance_from.unwrap_or(*maze.get_start_point());
+        let (width, height) = maze.get_dimensions();
+        if source.x >= width || source.y >= height || !maze.is_active(&source) {
+            eprintln!(
+                "Distance source ({}, {}) is not a cell of the maze",
+                source.x, source.y
+            );
+            exit(1);
+        }
+        let distance_map = solver::DistanceMap::new(&maze, &source);
+        let serialized = serde_json::to_string(&distance_map).unwrap();
+        fs::write(format!("{path_str}.json"), serialized).expect("Failed to write file");
+    }
+
+    if let Some(path_str) = &config.trace {
+        let solver = get_maze_solver(config.strategy, &config, &maze);
+        let mut rng = ChaCha8Rng::seed_from_u64(maze.get_seed());
+        let trace = solver.solve(&maze, &mut rng).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            exit(1);
+        });
+        let serialized = serde_json::to_string(&trace).unwrap();
+        fs::write(format!("{path_str}.json"), serialized).expect("Failed to write file");
     }
 
     if config.console_print {
@@ -114,9 +338,303 @@ pub fn main_run() {
     image.save(config.path_out).expect("Can't save file");
 }
 
-fn get_maze_generator(generator_type: GeneratorType) -> Box<dyn MazeGenerator> {
+///Generates the maze described by `config`, exits with the error if it can't be built
+fn build_square_maze(config: &CommandArgs) -> Maze {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let g_type = config.get_generator_type(seed);
+    let generator = get_maze_generator(g_type, config);
+    let mut builder = MazeBuilder::from_generator(generator)
+        .with_wrap(config.wrap)
+        .with_seed(seed);
+    if let Some(start) = config.start {
+        builder = builder.with_start(start);
+    }
+    if let Some(end) = config.end {
+        builder = builder.with_end(end);
+    }
+    for exit in config.exit.iter() {
+        builder = builder.with_exit(*exit);
+    }
+    for room in config.room.iter() {
+        builder = builder.with_room(*room);
+    }
+    builder = builder.with_locks(config.locks);
+    if let Some(fraction) = config.braid {
+        builder = builder.with_braid(fraction);
+    }
+    if let Some(mask_path) = &config.mask {
+        let mask = Mask::from_image(mask_path, config.width, config.height).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            exit(1);
+        });
+        builder = builder.with_mask(mask);
+    }
+    let mut maze = builder
+        .generate(config.width, config.height)
+        .unwrap_or_else(|err| {
+            eprintln!("{err}");
+            exit(1);
+        });
+    let algorithm = g_type.to_possible_value().expect("No skipped generator");
+    maze.set_algorithm(algorithm.get_name());
+    maze
+}
+
+fn run_hex(config: CommandArgs) {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let mut maze = HexMaze::new(config.width, config.height, seed);
+    generate_grid_maze(&config, &mut maze, seed);
+
+    let config_array = ConfigArray {
+        cell_width: config.cell_width,
+        cell_height: config.cell_height,
+    };
+
+    let mut image_builder = maze_image_builder::HexMazeImageBuilder::new(config_array, &maze);
+    image_builder.solve(config.solve);
+    let image = image_builder.build_image();
+    image.save(config.path_out).expect("Can't save file");
+}
+
+fn run_polar(config: CommandArgs) {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let mut maze = PolarMaze::new(config.height, seed);
+    generate_grid_maze(&config, &mut maze, seed);
+
+    let config_array = ConfigArray {
+        cell_width: config.cell_width,
+        cell_height: config.cell_height,
+    };
+
+    let mut image_builder = maze_image_builder::PolarMazeImageBuilder::new(config_array, &maze);
+    image_builder.solve(config.solve);
+    let image = image_builder.build_image();
+    image.save(config.path_out).expect("Can't save file");
+}
+
+fn run_triangle(config: CommandArgs) {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let mut maze = TriangleMaze::new(config.width, config.height, seed);
+    generate_grid_maze(&config, &mut maze, seed);
+
+    let config_array = ConfigArray {
+        cell_width: config.cell_width,
+        cell_height: config.cell_height,
+    };
+
+    let mut image_builder = maze_image_builder::TriangleMazeImageBuilder::new(config_array, &maze);
+    image_builder.solve(config.solve);
+    let image = image_builder.build_image();
+    image.save(config.path_out).expect("Can't save file");
+}
+
+fn run_3d(config: CommandArgs) {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let mut maze = Maze3D::new(config.width, config.height, config.depth as usize, seed);
+    generate_grid_maze(&config, &mut maze, seed);
+
+    let config_array = ConfigArray {
+        cell_width: config.cell_width,
+        cell_height: config.cell_height,
+    };
+
+    let mut image_builder = maze_image_builder::Maze3DImageBuilder::new(config_array, &maze);
+    image_builder.solve(config.solve);
+    let image = image_builder.build_image();
+    image.save(config.path_out).expect("Can't save file");
+}
+
+///Generation, serialization and the console check shared by the non-square topologies
+fn generate_grid_maze<G: Grid<Node: Serialize> + Serialize>(
+    config: &CommandArgs,
+    maze: &mut G,
+    seed: u64,
+) {
+    if config.mask.is_some() || config.braid.is_some() || config.wrap != Wrap::None {
+        eprintln!("--mask, --braid and --wrap are only available for single floor square mazes");
+        exit(1);
+    }
+    if config.start.is_some() || config.end.is_some() || !config.exit.is_empty() {
+        eprintln!("--start, --end and --exit are only available for single floor square mazes");
+        exit(1);
+    }
+    if !config.room.is_empty() || config.locks > 0 {
+        eprintln!("--room and --locks are only available for single floor square mazes");
+        exit(1);
+    }
+    if config.compare_solvers || config.distance_map.is_some() || config.trace.is_some() {
+        eprintln!(
+            "--compare-solvers, --distance-map and --trace are only available for single floor square mazes"
+        );
+        exit(1);
+    }
+    let result = match config.algorithm.unwrap_or(GeneratorType::Wilson) {
+        GeneratorType::Wilson => generate_with_seed(&WilsonGenerator, maze, seed),
+        GeneratorType::RecursiveBacktracker => {
+            generate_with_seed(&RecursiveBacktrackerGenerator, maze, seed)
+        }
+        GeneratorType::Kruskal => generate_with_seed(&KruskalGenerator, maze, seed),
+        GeneratorType::Prim => generate_with_seed(&PrimGenerator, maze, seed),
+        GeneratorType::AldousBroder => generate_with_seed(&AldousBroderGenerator, maze, seed),
+        GeneratorType::AldousBroderWilson => {
+            let generator = AldousBroderWilsonGenerator {
+                switch_fraction: config.switch_fraction,
+            };
+            generate_with_seed(&generator, maze, seed)
+        }
+        GeneratorType::HuntAndKill => generate_with_seed(&HuntAndKillGenerator, maze, seed),
+        GeneratorType::GrowingTree => {
+            let generator = GrowingTreeGenerator {
+                selector: config.selector.build(),
+            };
+            generate_with_seed(&generator, maze, seed)
+        }
+        algorithm => {
+            eprintln!("{algorithm:?} is only available for square mazes");
+            exit(1);
+        }
+    };
+    result.unwrap();
+
+    if let Some(path_str) = &config.serialize {
+        let path = solver::solve_maze(maze).unwrap();
+        serialize_maze(path_str, maze, &path);
+    }
+
+    if let Some(limit) = config.count_paths {
+        print_path_count(maze, limit);
+    }
+
+    if config.console_print {
+        eprintln!("Console output is only available for square mazes");
+        exit(1);
+    }
+}
+
+///Routes from the start to each exit, every exit is counted up to `limit` on its own
+fn print_path_count<G: Grid>(maze: &G, limit: usize) {
+    for end in maze.ends() {
+        let paths = solver::enumerate_paths(maze, &maze.start(), &end, limit);
+        let mut lengths: Vec<usize> = paths.iter().map(|path| path.length()).collect();
+        lengths.sort_unstable();
+        let at_least = if paths.len() == limit {
+            "at least "
+        } else {
+            ""
+        };
+        println!("{at_least}{} routes from start to {end:?}", paths.len());
+        println!("lengths: {lengths:?}");
+    }
+}
+
+fn print_solver_comparison(maze: &Maze) {
+    let (start, end) = (maze.get_start_point(), maze.get_end_point());
+    let mut searches = vec![("bfs".to_string(), solver::search_bfs(maze, start, end))];
+    for heuristic in solver::Heuristic::ALL {
+        let name = format!("a-star {heuristic:?}").to_lowercase();
+        searches.push((name, solver::search_a_star(maze, start, end, heuristic)));
+    }
+    for (name, search) in searches {
+        match search {
+            Ok(search) => println!(
+                "{name:<18} path length {:>6}, expanded {:>8}",
+                search.path.length(),
+                search.expanded
+            ),
+            Err(err) => println!("{name:<18} {err}"),
+        }
+    }
+}
+
+///Writes the maze into <path_str>.json and its solution into <path_str>_path.json
+fn serialize_maze<T: Serialize, C: Serialize>(path_str: &str, maze: &T, path: &solver::Path<C>) {
+    let serialized = serde_json::to_string(maze).unwrap();
+    fs::write(format!("{path_str}.json",), serialized).expect("Failed to write file");
+
+    let serialized = serde_json::to_string(path).unwrap();
+    fs::write(format!("{path_str}_path.json"), serialized).expect("Failed to write to file");
+}
+
+fn parse_fraction(value: &str) -> Result<f64, String> {
+    let fraction: f64 = value
+        .parse()
+        .map_err(|_| format!("`{value}` isn't a number"))?;
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(format!("{fraction} is not in 0.0-1.0"));
+    }
+    Ok(fraction)
+}
+
+fn get_maze_generator(
+    generator_type: GeneratorType,
+    config: &CommandArgs,
+) -> Box<dyn MazeGenerator> {
     match generator_type {
         GeneratorType::Wilson => Box::new(WilsonGenerator),
-        GeneratorType::Other => todo!("Implement Other generator"),
+        GeneratorType::RecursiveBacktracker => Box::new(RecursiveBacktrackerGenerator),
+        GeneratorType::Kruskal => Box::new(KruskalGenerator),
+        GeneratorType::Prim => Box::new(PrimGenerator),
+        GeneratorType::Eller => Box::new(EllerGenerator),
+        GeneratorType::AldousBroder => Box::new(AldousBroderGenerator),
+        GeneratorType::AldousBroderWilson => Box::new(AldousBroderWilsonGenerator {
+            switch_fraction: config.switch_fraction,
+        }),
+        GeneratorType::HuntAndKill => Box::new(HuntAndKillGenerator),
+        GeneratorType::BinaryTree => Box::new(BinaryTreeGenerator { bias: config.bias }),
+        GeneratorType::Sidewinder => Box::new(SidewinderGenerator { bias: config.bias }),
+        GeneratorType::GrowingTree => Box::new(GrowingTreeGenerator {
+            selector: config.selector.build(),
+        }),
+        GeneratorType::RecursiveDivision => Box::new(RecursiveDivisionGenerator),
+        GeneratorType::Weave => Box::new(WeaveGenerator {
+            crossing_probability: config.crossing_probability,
+        }),
+    }
+}
+
+fn get_maze_solver(solver_type: SolverType, config: &CommandArgs, maze: &Maze) -> Box<dyn Solver> {
+    match solver_type {
+        SolverType::WallFollowerLeft => Box::new(WallFollowerSolver { hand: Hand::Left }),
+        SolverType::WallFollowerRight => Box::new(WallFollowerSolver { hand: Hand::Right }),
+        SolverType::Tremaux => Box::new(TremauxSolver),
+        SolverType::DeadEndFilling => Box::new(DeadEndFillingSolver),
+        //every step is kept in the trace, the cap has to grow no faster than the maze
+        SolverType::RandomMouse => Box::new(RandomMouseSolver {
+            max_steps: config
+                .max_steps
+                .unwrap_or_else(|| maze.get_active_cells().len().saturating_mul(100)),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests_lib {
+    use super::{build_square_maze, CommandArgs};
+    use clap::Parser;
+
+    #[test]
+    pub fn test_seed_reproduces_the_random_algorithm() {
+        let config = CommandArgs::parse_from(["maze", "10", "10", "--seed", "7"]);
+        let first = build_square_maze(&config);
+        let second = build_square_maze(&config);
+        assert!(first.get_algorithm().is_some());
+        assert_eq!(first.get_algorithm(), second.get_algorithm());
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+
+        //some seeds have to pick another algorithm, or the draw would not depend on the seed
+        let algorithms: std::collections::HashSet<String> = (0..20)
+            .map(|seed| {
+                let seed = seed.to_string();
+                let config = CommandArgs::parse_from(["maze", "10", "10", "--seed", &seed]);
+                build_square_maze(&config)
+                    .get_algorithm()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert!(algorithms.len() > 1);
     }
 }
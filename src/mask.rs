@@ -0,0 +1,203 @@
+use crate::maze::Coords;
+use image::{GrayImage, ImageError};
+use std::collections::VecDeque;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MaskError {
+    #[error("Failed to load mask image: {0}")]
+    Image(#[from] ImageError),
+    #[error("Mask is {0}x{1} but the maze is {2}x{3}")]
+    SizeMismatch(usize, usize, usize, usize),
+    #[error("Mask has no active cell")]
+    Empty,
+    #[error("Active cells of the mask are not connected")]
+    NotConnected,
+}
+
+///Cells taking part in generation, everything outside of the mask stays out of the maze
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mask {
+    width: usize,
+    height: usize,
+    active: Vec<bool>,
+}
+
+impl Mask {
+    ///Mask with every cell active
+    pub fn new(width: usize, height: usize) -> Mask {
+        Mask {
+            width,
+            height,
+            active: vec![true; width * height],
+        }
+    }
+
+    ///Loads a black and white image, cells under white pixels are active.
+    ///The image is scaled to `width` x `height` cells, sampling the pixel in the middle of each cell.
+    pub fn from_image<P: AsRef<Path>>(
+        path: P,
+        width: usize,
+        height: usize,
+    ) -> Result<Mask, MaskError> {
+        let image = image::open(path)?.to_luma8();
+        Ok(Mask::from_luma(&image, width, height))
+    }
+
+    pub fn from_luma(image: &GrayImage, width: usize, height: usize) -> Mask {
+        let (image_width, image_height) = (image.width() as usize, image.height() as usize);
+        let mut mask = Mask::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let pixel_x = (2 * x + 1) * image_width / (2 * width);
+                let pixel_y = (2 * y + 1) * image_height / (2 * height);
+                let luma = image.get_pixel(pixel_x as u32, pixel_y as u32).0[0];
+                mask.set_active(&Coords { x, y }, luma >= 0x80);
+            }
+        }
+        mask
+    }
+
+    pub fn is_active(&self, coords: &Coords) -> bool {
+        self.active[coords.y * self.width + coords.x]
+    }
+
+    pub fn set_active(&mut self, coords: &Coords, active: bool) {
+        self.active[coords.y * self.width + coords.x] = active;
+    }
+
+    pub fn get_dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    ///Checks that there is at least one active cell and that all of them form one region,
+    ///otherwise no spanning tree can cover them
+    pub fn validate(&self) -> Result<(), MaskError> {
+        let start = (0..self.active.len())
+            .find(|index| self.active[*index])
+            .ok_or(MaskError::Empty)?;
+
+        let mut visited = vec![false; self.active.len()];
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+        let mut count = 1;
+        while let Some(index) = queue.pop_front() {
+            let (x, y) = (index % self.width, index / self.width);
+            let mut neighbours = Vec::new();
+            if x > 0 {
+                neighbours.push(index - 1);
+            }
+            if x < self.width - 1 {
+                neighbours.push(index + 1);
+            }
+            if y > 0 {
+                neighbours.push(index - self.width);
+            }
+            if y < self.height - 1 {
+                neighbours.push(index + self.width);
+            }
+            for neighbour in neighbours {
+                if self.active[neighbour] && !visited[neighbour] {
+                    visited[neighbour] = true;
+                    count += 1;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        if count != self.active.iter().filter(|active| **active).count() {
+            return Err(MaskError::NotConnected);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_mask {
+    use super::{Mask, MaskError};
+    use crate::maze::MazeGenerator;
+    use crate::maze::{Coords, MazeBuilder};
+    use crate::maze_generator::{
+        aldous_broder::AldousBroderWilsonGenerator, growing_tree::GrowingTreeGenerator,
+        growing_tree::RandomSelector, hunt_and_kill::HuntAndKillGenerator,
+        kruskal::KruskalGenerator, prim::PrimGenerator,
+        recursive_backtracker::RecursiveBacktrackerGenerator,
+        recursive_division::RecursiveDivisionGenerator, wilson::WilsonGenerator,
+    };
+    use crate::solver::solve_maze;
+    use image::{GrayImage, Luma};
+
+    ///Ring shaped image: white everywhere except a black square in the middle
+    fn ring_image() -> GrayImage {
+        GrayImage::from_fn(40, 40, |x, y| {
+            if (10..30).contains(&x) && (10..30).contains(&y) {
+                Luma([0x00])
+            } else {
+                Luma([0xff])
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_mask_from_image() {
+        let mask = Mask::from_luma(&ring_image(), 8, 8);
+        assert!(mask.is_active(&Coords { x: 0, y: 0 }));
+        assert!(!mask.is_active(&Coords { x: 4, y: 4 }));
+        assert!(mask.validate().is_ok());
+    }
+
+    #[test]
+    pub fn test_validate_rejects_split_mask() {
+        let mut mask = Mask::new(5, 3);
+        for y in 0..3 {
+            mask.set_active(&Coords { x: 2, y }, false);
+        }
+        assert!(matches!(mask.validate(), Err(MaskError::NotConnected)));
+    }
+
+    #[test]
+    pub fn test_generate_masked_maze() {
+        let mask = Mask::from_luma(&ring_image(), 8, 8);
+        let maze = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_mask(mask)
+            .generate(8, 8)
+            .unwrap();
+        assert_eq!(maze.count_active(), 8 * 8 - 4 * 4);
+        assert_eq!(maze.count_passages(), maze.count_active() - 1);
+        assert!(!maze.borrow_cell(&Coords { x: 4, y: 4 }).in_maze);
+        assert!(solve_maze(&maze).is_ok());
+    }
+
+    #[test]
+    pub fn test_generators_respect_mask() {
+        let generators: Vec<Box<dyn MazeGenerator>> = vec![
+            Box::new(RecursiveBacktrackerGenerator),
+            Box::new(KruskalGenerator),
+            Box::new(PrimGenerator),
+            Box::new(HuntAndKillGenerator),
+            Box::new(AldousBroderWilsonGenerator {
+                switch_fraction: 0.5,
+            }),
+            Box::new(GrowingTreeGenerator {
+                selector: Box::new(RandomSelector),
+            }),
+        ];
+        for generator in generators {
+            let maze = MazeBuilder::from_generator(generator)
+                .with_mask(Mask::from_luma(&ring_image(), 10, 10))
+                .generate(10, 10)
+                .unwrap();
+            assert_eq!(maze.count_passages(), maze.count_active() - 1);
+            assert!(solve_maze(&maze).is_ok());
+        }
+    }
+
+    #[test]
+    pub fn test_unsupported_generator_rejects_mask() {
+        let result = MazeBuilder::from_generator(Box::new(RecursiveDivisionGenerator))
+            .with_mask(Mask::from_luma(&ring_image(), 10, 10))
+            .generate(10, 10);
+        assert!(result.is_err());
+    }
+}
@@ -1,5 +1,16 @@
+use crate::braid::braid;
+use crate::grid::Grid;
+use crate::mask::{Mask, MaskError};
+use crate::maze_generator::random_index;
+use crate::puzzle::{place_locks, Lock};
+use crate::room::{open_rooms, reserve_rooms, validate_rooms, Room};
+use crate::solver::farthest_node;
+use clap::ValueEnum;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use std::io;
+use std::str::FromStr;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum LinkType {
@@ -24,6 +35,49 @@ pub struct Cell {
     pub left: LinkType,
     pub direction: Direction,
     pub in_maze: bool,
+    ///false for cells outside of the mask, they are never part of the maze
+    #[serde(default = "default_active")]
+    pub active: bool,
+    #[serde(default)]
+    pub crossing: Crossing,
+}
+
+///Passage running under a cell of a weave maze. The cell's own passage goes straight over it
+///along the other axis, the sides on the tunnel axis are open but lead past the cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Crossing {
+    #[default]
+    None,
+    ///Tunnel from top to bottom under a left to right passage
+    VerticalUnder,
+    ///Tunnel from left to right under a top to bottom passage
+    HorizontalUnder,
+}
+
+impl Crossing {
+    ///True if the side in `direction` belongs to the tunnel and not to the cell
+    pub fn is_under(&self, direction: &Direction) -> bool {
+        match self {
+            Crossing::None => false,
+            Crossing::VerticalUnder => matches!(direction, Direction::Up | Direction::Down),
+            Crossing::HorizontalUnder => matches!(direction, Direction::Left | Direction::Right),
+        }
+    }
+}
+
+fn default_active() -> bool {
+    true
+}
+
+///Edges of the grid that are adjacent to the opposite edge
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum Wrap {
+    #[default]
+    None,
+    ///Left and right edges are joined
+    Cylinder,
+    ///Left and right edges are joined, and so are top and bottom
+    Torus,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,6 +87,18 @@ pub struct Maze {
     height: usize,
     start_point: Coords,
     end_point: Coords,
+    #[serde(default)]
+    seed: u64,
+    ///Name of the generation algorithm, the seed only reproduces the maze together with it
+    #[serde(default)]
+    algorithm: Option<String>,
+    #[serde(default)]
+    wrap: Wrap,
+    ///Exits besides `end_point`
+    #[serde(default)]
+    exits: Vec<Coords>,
+    #[serde(default)]
+    locks: Vec<Lock>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -41,39 +107,311 @@ pub struct Coords {
     pub y: usize,
 }
 
+///Parses `x,y`
+impl FromStr for Coords {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Coords, String> {
+        let (x, y) = s
+            .split_once(',')
+            .ok_or(format!("`{s}` is not in the x,y format"))?;
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid coordinate `{value}`"))
+        };
+        Ok(Coords {
+            x: parse(x)?,
+            y: parse(y)?,
+        })
+    }
+}
+
+///Where the start or the end of a maze goes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    At(Coords),
+    ///As far as possible from the other point, if both are auto the two cells
+    ///farthest apart, the diameter of the maze
+    Auto,
+}
+
+///Parses `auto` or `x,y`
+impl FromStr for Placement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Placement, String> {
+        match s.trim() {
+            "auto" => Ok(Placement::Auto),
+            coords => Ok(Placement::At(coords.parse()?)),
+        }
+    }
+}
+
+impl Direction {
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Blank => Direction::Blank,
+        }
+    }
+}
+
+impl Cell {
+    ///Cell closed on all four sides, the starting state for carving generators
+    pub fn walled() -> Cell {
+        Cell {
+            top: LinkType::Wall,
+            right: LinkType::Wall,
+            bottom: LinkType::Wall,
+            left: LinkType::Wall,
+            in_maze: false,
+            direction: Direction::Blank,
+            active: true,
+            crossing: Crossing::None,
+        }
+    }
+
+    pub fn get_link(&self, direction: &Direction) -> &LinkType {
+        match direction {
+            Direction::Up => &self.top,
+            Direction::Right => &self.right,
+            Direction::Down => &self.bottom,
+            Direction::Left => &self.left,
+            Direction::Blank => &LinkType::Wall,
+        }
+    }
+
+    pub fn set_link(&mut self, direction: &Direction, link_type: LinkType) {
+        match direction {
+            Direction::Up => self.top = link_type,
+            Direction::Right => self.right = link_type,
+            Direction::Down => self.bottom = link_type,
+            Direction::Left => self.left = link_type,
+            Direction::Blank => {}
+        }
+    }
+}
+
 pub trait MazeGenerator {
-    fn generate(&self, maze: &mut Maze) -> Result<(), io::Error>;
+    ///Link between neighbouring cells before `generate` runs, the outer border is always a wall.
+    ///Carving generators start from walls, wall-adding generators from an open field.
+    fn initial_link(&self) -> LinkType {
+        LinkType::Wall
+    }
+
+    ///All randomness has to come from `rng`, so that a seed reproduces the same maze
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error>;
 }
 
 impl Maze {
     pub fn get_directions_possible(&self, coords: &Coords) -> Vec<Direction> {
         let mut vec: Vec<Direction> = Vec::new();
+        let wraps_horizontally = self.wraps_horizontally();
+        let wraps_vertically = self.wraps_vertically();
 
-        if coords.x > 0 {
+        if coords.x > 0 || wraps_horizontally {
             vec.push(Direction::Left);
         }
 
-        if coords.y > 0 {
+        if coords.y > 0 || wraps_vertically {
             vec.push(Direction::Up);
         }
 
-        if coords.x < self.width - 1 {
+        if coords.x < self.width - 1 || wraps_horizontally {
             vec.push(Direction::Right);
         }
 
-        if coords.y < self.height - 1 {
+        if coords.y < self.height - 1 || wraps_vertically {
             vec.push(Direction::Down);
         }
 
+        vec.retain(|direction| {
+            self.borrow_cell(&self.get_neighbour(coords, direction))
+                .active
+        });
         vec
     }
 
+    ///Left and right edges are adjacent, a grid narrower than 3 cells never wraps
+    ///as both sides of a cell would lead to the same neighbour
+    pub fn wraps_horizontally(&self) -> bool {
+        self.wrap != Wrap::None && self.width > 2
+    }
+
+    ///Top and bottom edges are adjacent, a grid lower than 3 cells never wraps
+    pub fn wraps_vertically(&self) -> bool {
+        self.wrap == Wrap::Torus && self.height > 2
+    }
+
+    ///True if some border cells are adjacent to the opposite border
+    pub fn is_wrapping(&self) -> bool {
+        self.wraps_horizontally() || self.wraps_vertically()
+    }
+
+    pub fn get_wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    ///Columns and rows between two cells, going around the wrapped edges when that is shorter
+    pub fn get_offset(&self, a: &Coords, b: &Coords) -> (usize, usize) {
+        let mut dx = a.x.abs_diff(b.x);
+        let mut dy = a.y.abs_diff(b.y);
+        if self.wraps_horizontally() {
+            dx = dx.min(self.width - dx);
+        }
+        if self.wraps_vertically() {
+            dy = dy.min(self.height - dy);
+        }
+        (dx, dy)
+    }
+
+    pub fn is_active(&self, coords: &Coords) -> bool {
+        self.borrow_cell(coords).active
+    }
+
+    pub fn count_active(&self) -> usize {
+        self.data
+            .iter()
+            .flatten()
+            .filter(|cell| cell.active)
+            .count()
+    }
+
+    ///True if a mask removed some of the cells
+    pub fn is_masked(&self) -> bool {
+        self.count_active() < self.width * self.height
+    }
+
+    ///Coords of all cells taking part in the maze, row by row
+    pub fn get_active_cells(&self) -> Vec<Coords> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Coords { x, y }))
+            .filter(|coords| self.is_active(coords))
+            .collect()
+    }
+
+    ///Directions leading to neighbours that are not yet part of the maze
+    pub fn get_unvisited_directions(&self, coords: &Coords) -> Vec<Direction> {
+        self.get_directions_possible(coords)
+            .into_iter()
+            .filter(|direction| {
+                !self
+                    .borrow_cell(&self.get_neighbour(coords, direction))
+                    .in_maze
+            })
+            .collect()
+    }
+
+    ///Coords of the neighbour in `direction`, the caller has to make sure it exists.
+    ///Leaving a wrapping grid on one side re-enters it from the opposite side.
+    pub fn get_neighbour(&self, coords: &Coords, direction: &Direction) -> Coords {
+        match direction {
+            Direction::Up => Coords {
+                x: coords.x,
+                y: (coords.y + self.height - 1) % self.height,
+            },
+            Direction::Right => Coords {
+                x: (coords.x + 1) % self.width,
+                y: coords.y,
+            },
+            Direction::Down => Coords {
+                x: coords.x,
+                y: (coords.y + 1) % self.height,
+            },
+            Direction::Left => Coords {
+                x: (coords.x + self.width - 1) % self.width,
+                y: coords.y,
+            },
+            Direction::Blank => *coords,
+        }
+    }
+
+    ///Direction leading from `from` to the adjacent cell `to`
+    pub fn direction_to(&self, from: &Coords, to: &Coords) -> Option<Direction> {
+        self.get_directions_possible(from)
+            .into_iter()
+            .find(|direction| self.get_neighbour(from, direction) == *to)
+    }
+
+    ///Removes the wall between `coords` and its neighbour in `direction` and returns the neighbour
+    pub fn carve_path(&mut self, coords: &Coords, direction: &Direction) -> Coords {
+        let neighbour = self.get_neighbour(coords, direction);
+        self.borrow_cell_mut(coords)
+            .set_link(direction, LinkType::Path);
+        self.borrow_cell_mut(&neighbour)
+            .set_link(&direction.opposite(), LinkType::Path);
+        neighbour
+    }
+
+    ///Puts the wall between `coords` and its neighbour in `direction` back
+    pub fn close_passage(&mut self, coords: &Coords, direction: &Direction) {
+        let neighbour = self.get_neighbour(coords, direction);
+        self.borrow_cell_mut(coords)
+            .set_link(direction, LinkType::Wall);
+        self.borrow_cell_mut(&neighbour)
+            .set_link(&direction.opposite(), LinkType::Wall);
+    }
+
+    ///Deactivates the cells outside of `mask` and moves start and end onto active cells
+    fn apply_mask(&mut self, mask: &Mask) -> Result<(), MaskError> {
+        let (mask_width, mask_height) = mask.get_dimensions();
+        if (mask_width, mask_height) != (self.width, self.height) {
+            return Err(MaskError::SizeMismatch(
+                mask_width,
+                mask_height,
+                self.width,
+                self.height,
+            ));
+        }
+        mask.validate()?;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let coords = Coords { x, y };
+                self.borrow_cell_mut(&coords).active = mask.is_active(&coords);
+            }
+        }
+
+        let active_cells = self.get_active_cells();
+        self.start_point = active_cells[0];
+        self.end_point = active_cells[active_cells.len() - 1];
+        Ok(())
+    }
+
+    ///Replaces every wall between two cells with a path, keeping the outer border
+    fn open_interior(&mut self) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let coords = Coords { x, y };
+                for direction in self.get_directions_possible(&coords) {
+                    self.borrow_cell_mut(&coords)
+                        .set_link(&direction, LinkType::Path);
+                }
+            }
+        }
+    }
+
     pub fn print_to_console(&self) {
         let mut lines: Vec<Vec<char>> = vec![vec![]; self.height * 2 + 1];
-        lines[0].extend(['+', '-', '-', '-'].iter().cycle().take(self.width * 4 + 1));
+        lines[0].push('+');
+        for x in 0..self.width {
+            let top = match self.data[0][x].top {
+                LinkType::Path => ' ',
+                LinkType::Wall => '-',
+            };
+            lines[0].extend([top, top, top, '+'].iter());
+        }
         for y in 0..self.height {
             let print_y = y * 2 + 1;
-            lines[print_y].push('|');
+            lines[print_y].push(match self.data[y][0].left {
+                LinkType::Path => ' ',
+                LinkType::Wall => '|',
+            });
             lines[print_y + 1].push('+');
             for x in 0..self.width {
                 let right = match self.data[y][x].right {
@@ -106,37 +444,46 @@ impl Maze {
     }
 
     pub fn get_possible_moves(&self, coords: &Coords) -> Vec<Coords> {
-        let mut ret_vec = Vec::new();
-
-        if self.data[coords.y][coords.x].top == LinkType::Path {
-            ret_vec.push(Coords {
-                x: coords.x,
-                y: coords.y - 1,
-            });
-        }
-
-        if self.data[coords.y][coords.x].right == LinkType::Path {
-            ret_vec.push(Coords {
-                x: coords.x + 1,
-                y: coords.y,
-            });
-        }
-
-        if self.data[coords.y][coords.x].bottom == LinkType::Path {
-            ret_vec.push(Coords {
-                x: coords.x,
-                y: coords.y + 1,
-            });
-        }
+        self.get_moves_with_directions(coords)
+            .into_iter()
+            .map(|(_, neighbour)| neighbour)
+            .collect()
+    }
 
-        if self.data[coords.y][coords.x].left == LinkType::Path {
-            ret_vec.push(Coords {
-                x: coords.x - 1,
-                y: coords.y,
-            });
-        }
+    ///Cells reachable in one move, with the direction the move leaves in.
+    ///A passage leading into a tunnel ends at the first cell past the crossings.
+    pub fn get_moves_with_directions(&self, coords: &Coords) -> Vec<(Direction, Coords)> {
+        let cell = self.borrow_cell(coords);
+        [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ]
+        .into_iter()
+        .filter(|direction| {
+            cell.get_link(direction) == &LinkType::Path && !cell.crossing.is_under(direction)
+        })
+        .map(|direction| {
+            let mut neighbour = self.get_neighbour(coords, &direction);
+            while self.borrow_cell(&neighbour).crossing.is_under(&direction) {
+                neighbour = self.get_neighbour(&neighbour, &direction);
+            }
+            (direction, neighbour)
+        })
+        .collect()
+    }
 
-        ret_vec
+    ///Number of removed walls, a perfect maze has exactly `width * height - 1`
+    #[cfg(test)]
+    pub fn count_passages(&self) -> usize {
+        self.data
+            .iter()
+            .flatten()
+            .map(|cell| {
+                (cell.right == LinkType::Path) as usize + (cell.bottom == LinkType::Path) as usize
+            })
+            .sum()
     }
 
     pub fn get_dimensions(&self) -> (usize, usize) {
@@ -158,40 +505,467 @@ impl Maze {
     pub fn get_end_point(&self) -> &Coords {
         &self.end_point
     }
+
+    ///Locked doors and their keys, see [`place_locks`]
+    pub fn get_locks(&self) -> &[Lock] {
+        &self.locks
+    }
+
+    pub fn set_locks(&mut self, locks: Vec<Lock>) {
+        self.locks = locks;
+    }
+
+    ///End point followed by the additional exits
+    pub fn get_exits(&self) -> Vec<Coords> {
+        let mut exits = vec![self.end_point];
+        exits.extend(self.exits.iter().filter(|exit| **exit != self.end_point));
+        exits
+    }
+
+    ///Sides of the cell on the outer wall of the maze, the border of the grid or of the mask
+    pub fn get_outer_sides(&self, coords: &Coords) -> Vec<Direction> {
+        let inner = self.get_directions_possible(coords);
+        [
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+        ]
+        .into_iter()
+        .filter(|direction| !inner.contains(direction))
+        .collect()
+    }
+
+    ///Error if `coords` is not an active cell of the maze
+    fn check_in_maze(&self, coords: &Coords) -> Result<(), io::Error> {
+        if coords.x >= self.width || coords.y >= self.height || !self.is_active(coords) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("({}, {}) is not a cell of the maze", coords.x, coords.y),
+            ));
+        }
+        Ok(())
+    }
+
+    ///Puts the start and end at the fixed placements, auto placements are resolved after generation
+    fn place_fixed(
+        &mut self,
+        start: Option<Placement>,
+        end: Option<Placement>,
+    ) -> Result<(), io::Error> {
+        if let Some(Placement::At(coords)) = start {
+            self.check_in_maze(&coords)?;
+            self.start_point = coords;
+        }
+        if let Some(Placement::At(coords)) = end {
+            self.check_in_maze(&coords)?;
+            self.end_point = coords;
+        }
+        Ok(())
+    }
+
+    ///Moves the auto placed points as far apart as the passages allow. Run on the spanning tree,
+    ///the two sweeps of a breadth-first search with both on auto find its diameter.
+    ///Returns false without moving anything if the fixed point to measure from is inactive.
+    fn place_auto(&mut self, start: Option<Placement>, end: Option<Placement>) -> bool {
+        match (start, end) {
+            (Some(Placement::Auto), Some(Placement::Auto)) => {
+                let origin = if self.is_active(&self.start_point) {
+                    self.start_point
+                } else {
+                    self.get_active_cells()[0]
+                };
+                let (first, _) = farthest_node(self, &origin);
+                let (second, _) = farthest_node(self, &first);
+                self.start_point = first;
+                self.end_point = second;
+            }
+            (Some(Placement::Auto), _) if !self.is_active(&self.end_point) => return false,
+            (Some(Placement::Auto), _) => self.start_point = farthest_node(self, &self.end_point).0,
+            (_, Some(Placement::Auto)) if !self.is_active(&self.start_point) => return false,
+            (_, Some(Placement::Auto)) => self.end_point = farthest_node(self, &self.start_point).0,
+            _ => {}
+        }
+        true
+    }
+
+    ///Seed the maze was generated from
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_algorithm(&self) -> Option<&str> {
+        self.algorithm.as_deref()
+    }
+
+    pub fn set_algorithm(&mut self, algorithm: &str) {
+        self.algorithm = Some(algorithm.to_string());
+    }
+}
+
+impl Grid for Maze {
+    type Node = Coords;
+
+    fn nodes(&self) -> Vec<Coords> {
+        self.get_active_cells()
+    }
+
+    fn neighbours(&self, coords: &Coords) -> Vec<Coords> {
+        self.get_directions_possible(coords)
+            .iter()
+            .map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn links(&self, coords: &Coords) -> Vec<Coords> {
+        self.get_possible_moves(coords)
+    }
+
+    fn link(&mut self, a: &Coords, b: &Coords) {
+        if let Some(direction) = self.direction_to(a, b) {
+            self.carve_path(a, &direction);
+        }
+    }
+
+    fn contains(&self, coords: &Coords) -> bool {
+        coords.x < self.width && coords.y < self.height && self.is_active(coords)
+    }
+
+    fn is_in_maze(&self, coords: &Coords) -> bool {
+        self.borrow_cell(coords).in_maze
+    }
+
+    fn set_in_maze(&mut self, coords: &Coords, in_maze: bool) {
+        self.borrow_cell_mut(coords).in_maze = in_maze;
+    }
+
+    fn start(&self) -> Coords {
+        self.start_point
+    }
+
+    fn end(&self) -> Coords {
+        self.end_point
+    }
+
+    fn ends(&self) -> Vec<Coords> {
+        self.get_exits()
+    }
+
+    fn random_node(&self, rng: &mut dyn RngCore) -> Coords {
+        if self.is_masked() {
+            let active_cells = self.get_active_cells();
+            return active_cells[random_index(rng, active_cells.len())];
+        }
+        Coords {
+            x: random_index(rng, self.width),
+            y: random_index(rng, self.height),
+        }
+    }
 }
 
 pub struct MazeBuilder {
     generator: Box<dyn MazeGenerator>,
+    seed: Option<u64>,
+    braid: f64,
+    mask: Option<Mask>,
+    wrap: Wrap,
+    start: Option<Placement>,
+    end: Option<Placement>,
+    exits: Vec<Coords>,
+    rooms: Vec<Room>,
+    locks: usize,
 }
 
 impl MazeBuilder {
     pub fn from_generator(generator: Box<dyn MazeGenerator>) -> MazeBuilder {
         MazeBuilder {
-            generator: generator,
+            generator,
+            seed: None,
+            braid: 0.0,
+            mask: None,
+            wrap: Wrap::None,
+            start: None,
+            end: None,
+            exits: Vec::new(),
+            rooms: Vec::new(),
+            locks: 0,
         }
     }
+
+    ///Makes generation deterministic, a random seed is drawn when none is set
+    pub fn with_seed(mut self, seed: u64) -> MazeBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    ///Fraction (0.0-1.0) of dead ends removed after generation, see [`braid`]
+    pub fn with_braid(mut self, fraction: f64) -> MazeBuilder {
+        self.braid = fraction;
+        self
+    }
+
+    ///Only cells active in `mask` take part in the maze, its size has to match the maze
+    pub fn with_mask(mut self, mask: Mask) -> MazeBuilder {
+        self.mask = Some(mask);
+        self
+    }
+
+    ///Joins opposite edges of the grid, see [`Wrap`]
+    pub fn with_wrap(mut self, wrap: Wrap) -> MazeBuilder {
+        self.wrap = wrap;
+        self
+    }
+
+    ///Start point, the top left cell by default
+    pub fn with_start(mut self, start: Placement) -> MazeBuilder {
+        self.start = Some(start);
+        self
+    }
+
+    ///End point, the bottom right cell by default
+    pub fn with_end(mut self, end: Placement) -> MazeBuilder {
+        self.end = Some(end);
+        self
+    }
+
+    ///Additional exit, the solution leads to the exit closest to the start
+    pub fn with_exit(mut self, exit: Coords) -> MazeBuilder {
+        self.exits.push(exit);
+        self
+    }
+
+    ///Open rectangular area kept out of the generation and joined to the maze through
+    ///its doors, see [`reserve_rooms`] and [`open_rooms`]
+    pub fn with_room(mut self, room: Room) -> MazeBuilder {
+        self.rooms.push(room);
+        self
+    }
+
+    ///Number of locked doors placed on the route to the exit, each with a key
+    ///reachable before it, see [`place_locks`]
+    pub fn with_locks(mut self, count: usize) -> MazeBuilder {
+        self.locks = count;
+        self
+    }
+
     pub fn generate(&self, width: usize, height: usize) -> Result<Maze, io::Error> {
-        let base_cell = Cell {
-            top: LinkType::Wall,
-            right: LinkType::Wall,
-            bottom: LinkType::Wall,
-            left: LinkType::Wall,
-            in_maze: false,
-            direction: Direction::Blank,
-        };
+        let base_cell = Cell::walled();
+        let seed = self.seed.unwrap_or_else(rand::random);
 
         let mut maze = Maze {
-            width: width,
-            height: height,
+            width,
+            height,
             data: vec![vec![base_cell; width]; height],
             start_point: Coords { x: 0, y: 0 },
             end_point: Coords {
                 x: width - 1,
                 y: height - 1,
             },
+            seed,
+            algorithm: None,
+            wrap: self.wrap,
+            exits: self.exits.clone(),
+            locks: Vec::new(),
         };
 
-        self.generator.generate(&mut maze)?;
+        if let Some(mask) = &self.mask {
+            maze.apply_mask(mask)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        }
+        maze.place_fixed(self.start, self.end)?;
+        for exit in self.exits.iter() {
+            maze.check_in_maze(exit)?;
+        }
+        validate_rooms(&maze, &self.rooms)?;
+        reserve_rooms(&mut maze, &self.rooms)?;
+        //generators grow the maze from the start, it has to be outside of the rooms meanwhile
+        let start_point = maze.start_point;
+        if !maze.is_active(&start_point) {
+            maze.start_point = maze.get_active_cells()[0];
+        }
+
+        if self.generator.initial_link() == LinkType::Path {
+            maze.open_interior();
+        }
+
+        //ChaCha8 has a stable, platform independent output for a given seed
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        self.generator.generate(&mut maze, &mut rng)?;
+        maze.start_point = start_point;
+        //still a spanning tree here, braiding and rooms only add loops to it
+        let placed = maze.place_auto(self.start, self.end);
+        //braiding before the rooms are back keeps it from adding doors
+        if self.braid > 0.0 {
+            braid(&mut maze, self.braid, &mut rng);
+        }
+        open_rooms(&mut maze, &self.rooms, &mut rng);
+        if !placed {
+            //the fixed point is inside a room, it can only be measured from with the rooms open
+            maze.place_auto(self.start, self.end);
+        }
+        if self.locks > 0 {
+            place_locks(&mut maze, self.locks, &mut rng);
+        }
         Ok(maze)
     }
 }
+
+#[cfg(test)]
+mod tests_maze {
+    use super::{Coords, MazeBuilder, MazeGenerator, Placement, Wrap};
+    use crate::grid::Grid;
+    use crate::maze_generator::{
+        eller::EllerGenerator,
+        growing_tree::{GrowingTreeGenerator, SelectorSpec},
+        kruskal::KruskalGenerator,
+        recursive_division::RecursiveDivisionGenerator,
+        wilson::WilsonGenerator,
+    };
+    use crate::solver::{farthest_node, solve_maze};
+
+    fn generators() -> Vec<fn() -> Box<dyn MazeGenerator>> {
+        vec![
+            || Box::new(WilsonGenerator),
+            || Box::new(KruskalGenerator),
+            || Box::new(EllerGenerator),
+            || Box::new(RecursiveDivisionGenerator),
+            || {
+                Box::new(GrowingTreeGenerator {
+                    selector: "newest:3,random:1".parse::<SelectorSpec>().unwrap().build(),
+                })
+            },
+        ]
+    }
+
+    #[test]
+    pub fn test_same_seed_gives_same_maze() {
+        for generator in generators() {
+            let first = MazeBuilder::from_generator(generator())
+                .with_seed(42)
+                .generate(20, 20)
+                .unwrap();
+            let second = MazeBuilder::from_generator(generator())
+                .with_seed(42)
+                .generate(20, 20)
+                .unwrap();
+            assert_eq!(first.get_seed(), 42);
+            assert_eq!(
+                serde_json::to_string(&first).unwrap(),
+                serde_json::to_string(&second).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_different_seed_gives_different_maze() {
+        let first = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(1)
+            .generate(20, 20)
+            .unwrap();
+        let second = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(2)
+            .generate(20, 20)
+            .unwrap();
+        assert_ne!(first.data, second.data);
+    }
+
+    #[test]
+    pub fn test_wrapped_maze_crosses_the_border() {
+        let maze = MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_seed(3)
+            .with_wrap(Wrap::Torus)
+            .generate(10, 8)
+            .unwrap();
+        assert_eq!(
+            maze.neighbours(&Coords { x: 0, y: 0 }),
+            vec![
+                Coords { x: 9, y: 0 },
+                Coords { x: 0, y: 7 },
+                Coords { x: 1, y: 0 },
+                Coords { x: 0, y: 1 }
+            ]
+        );
+        assert_eq!(maze.count_passages(), 10 * 8 - 1);
+        for coords in maze.nodes() {
+            for neighbour in maze.get_possible_moves(&coords) {
+                assert!(maze.get_possible_moves(&neighbour).contains(&coords));
+            }
+        }
+        assert!(solve_maze(&maze).is_ok());
+
+        let cylinder = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_wrap(Wrap::Cylinder)
+            .generate(10, 8)
+            .unwrap();
+        assert_eq!(cylinder.neighbours(&Coords { x: 0, y: 0 }).len(), 3);
+        assert!(MazeBuilder::from_generator(Box::new(EllerGenerator))
+            .with_wrap(Wrap::Cylinder)
+            .generate(10, 8)
+            .is_err());
+    }
+
+    #[test]
+    pub fn test_start_end_and_exits() {
+        let maze = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(5)
+            .with_start(Placement::At(Coords { x: 3, y: 4 }))
+            .with_end(Placement::At(Coords { x: 9, y: 0 }))
+            .with_exit(Coords { x: 0, y: 9 })
+            .generate(10, 10)
+            .unwrap();
+        assert_eq!(maze.get_start_point(), &Coords { x: 3, y: 4 });
+        assert_eq!(
+            maze.get_exits(),
+            vec![Coords { x: 9, y: 0 }, Coords { x: 0, y: 9 }]
+        );
+        let path = solve_maze(&maze).unwrap();
+        assert!(maze.get_exits().contains(path.data.last().unwrap()));
+
+        assert!(MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_end(Placement::At(Coords { x: 10, y: 0 }))
+            .generate(10, 10)
+            .is_err());
+        assert_eq!("auto".parse::<Placement>(), Ok(Placement::Auto));
+        assert_eq!(
+            " 4, 7".parse::<Placement>(),
+            Ok(Placement::At(Coords { x: 4, y: 7 }))
+        );
+    }
+
+    #[test]
+    pub fn test_auto_placement_spans_the_diameter() {
+        let maze = MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_seed(6)
+            .with_start(Placement::Auto)
+            .with_end(Placement::Auto)
+            .generate(15, 12)
+            .unwrap();
+        let length = solve_maze(&maze).unwrap().data.len() - 1;
+        //in a tree no two cells are further apart than the ends of the diameter
+        for coords in maze.nodes() {
+            assert!(farthest_node(&maze, &coords).1 <= length);
+        }
+    }
+
+    #[test]
+    pub fn test_auto_placement_on_braided_maze_uses_the_tree() {
+        let build = |braid: f64| {
+            MazeBuilder::from_generator(Box::new(KruskalGenerator))
+                .with_seed(6)
+                .with_braid(braid)
+                .with_start(Placement::Auto)
+                .with_end(Placement::Auto)
+                .generate(15, 12)
+                .unwrap()
+        };
+        let (tree, braided) = (build(0.0), build(1.0));
+        assert!(braided.count_passages() > tree.count_passages());
+        assert_eq!(braided.get_start_point(), tree.get_start_point());
+        assert_eq!(braided.get_end_point(), tree.get_end_point());
+        //the points span the diameter of the tree the braided maze was made from
+        let length = solve_maze(&tree).unwrap().data.len() - 1;
+        for coords in tree.nodes() {
+            assert!(farthest_node(&tree, &coords).1 <= length);
+        }
+    }
+}
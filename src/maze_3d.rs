@@ -0,0 +1,291 @@
+use crate::grid::Grid;
+use crate::maze::LinkType;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Coords3D {
+    pub x: usize,
+    pub y: usize,
+    ///Floor, 0 being the ground floor
+    pub z: usize,
+}
+
+///Sides of a cell, the four walls of its floor plus the stairs up and down
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction3D {
+    West,
+    North,
+    East,
+    South,
+    Upstairs,
+    Downstairs,
+}
+
+impl Direction3D {
+    pub const ALL: [Direction3D; 6] = [
+        Direction3D::West,
+        Direction3D::North,
+        Direction3D::East,
+        Direction3D::South,
+        Direction3D::Upstairs,
+        Direction3D::Downstairs,
+    ];
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn opposite(&self) -> Direction3D {
+        match self {
+            Direction3D::West => Direction3D::East,
+            Direction3D::North => Direction3D::South,
+            Direction3D::East => Direction3D::West,
+            Direction3D::South => Direction3D::North,
+            Direction3D::Upstairs => Direction3D::Downstairs,
+            Direction3D::Downstairs => Direction3D::Upstairs,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cell3D {
+    ///Link on each side, indexed by [`Direction3D::index`]
+    pub links: [LinkType; 6],
+    pub in_maze: bool,
+}
+
+impl Cell3D {
+    pub fn get_link(&self, direction: &Direction3D) -> &LinkType {
+        &self.links[direction.index()]
+    }
+}
+
+///Stack of square floors, a cell can also be linked to the cells right above and below it.
+///The maze is entered on the ground floor and solved on the top floor.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Maze3D {
+    ///Indexed by floor, then row, then column
+    data: Vec<Vec<Vec<Cell3D>>>,
+    width: usize,
+    height: usize,
+    depth: usize,
+    start_point: Coords3D,
+    end_point: Coords3D,
+    seed: u64,
+}
+
+impl Maze3D {
+    ///`depth` floors of `width` x `height` cells with every wall standing,
+    ///ready for a [`crate::grid::GridGenerator`]
+    pub fn new(width: usize, height: usize, depth: usize, seed: u64) -> Maze3D {
+        let base_cell = Cell3D {
+            links: [
+                LinkType::Wall,
+                LinkType::Wall,
+                LinkType::Wall,
+                LinkType::Wall,
+                LinkType::Wall,
+                LinkType::Wall,
+            ],
+            in_maze: false,
+        };
+
+        Maze3D {
+            data: vec![vec![vec![base_cell; width]; height]; depth],
+            width,
+            height,
+            depth,
+            start_point: Coords3D { x: 0, y: 0, z: 0 },
+            end_point: Coords3D {
+                x: width - 1,
+                y: height - 1,
+                z: depth - 1,
+            },
+            seed,
+        }
+    }
+
+    pub fn borrow_cell(&self, coords: &Coords3D) -> &Cell3D {
+        &self.data[coords.z][coords.y][coords.x]
+    }
+
+    pub fn borrow_cell_mut(&mut self, coords: &Coords3D) -> &mut Cell3D {
+        &mut self.data[coords.z][coords.y][coords.x]
+    }
+
+    ///Neighbour in `direction`, None at the border of a floor and above the top floor
+    pub fn get_neighbour(&self, coords: &Coords3D, direction: &Direction3D) -> Option<Coords3D> {
+        let Coords3D { x, y, z } = *coords;
+        match direction {
+            Direction3D::West if x > 0 => Some(Coords3D { x: x - 1, y, z }),
+            Direction3D::North if y > 0 => Some(Coords3D { x, y: y - 1, z }),
+            Direction3D::East if x + 1 < self.width => Some(Coords3D { x: x + 1, y, z }),
+            Direction3D::South if y + 1 < self.height => Some(Coords3D { x, y: y + 1, z }),
+            Direction3D::Upstairs if z + 1 < self.depth => Some(Coords3D { x, y, z: z + 1 }),
+            Direction3D::Downstairs if z > 0 => Some(Coords3D { x, y, z: z - 1 }),
+            _ => None,
+        }
+    }
+
+    pub fn get_directions_possible(&self, coords: &Coords3D) -> Vec<Direction3D> {
+        Direction3D::ALL
+            .into_iter()
+            .filter(|direction| self.get_neighbour(coords, direction).is_some())
+            .collect()
+    }
+
+    pub fn direction_to(&self, from: &Coords3D, to: &Coords3D) -> Option<Direction3D> {
+        Direction3D::ALL
+            .into_iter()
+            .find(|direction| self.get_neighbour(from, direction) == Some(*to))
+    }
+
+    pub fn get_dimensions(&self) -> (usize, usize, usize) {
+        (self.width, self.height, self.depth)
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn get_start_point(&self) -> &Coords3D {
+        &self.start_point
+    }
+
+    pub fn get_end_point(&self) -> &Coords3D {
+        &self.end_point
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Grid for Maze3D {
+    type Node = Coords3D;
+
+    fn nodes(&self) -> Vec<Coords3D> {
+        (0..self.depth)
+            .flat_map(|z| {
+                (0..self.height)
+                    .flat_map(move |y| (0..self.width).map(move |x| Coords3D { x, y, z }))
+            })
+            .collect()
+    }
+
+    fn neighbours(&self, coords: &Coords3D) -> Vec<Coords3D> {
+        Direction3D::ALL
+            .iter()
+            .filter_map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn links(&self, coords: &Coords3D) -> Vec<Coords3D> {
+        let cell = self.borrow_cell(coords);
+        Direction3D::ALL
+            .iter()
+            .filter(|direction| cell.get_link(direction) == &LinkType::Path)
+            .filter_map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn link(&mut self, a: &Coords3D, b: &Coords3D) {
+        if let Some(direction) = self.direction_to(a, b) {
+            self.borrow_cell_mut(a).links[direction.index()] = LinkType::Path;
+            self.borrow_cell_mut(b).links[direction.opposite().index()] = LinkType::Path;
+        }
+    }
+
+    fn is_in_maze(&self, coords: &Coords3D) -> bool {
+        self.borrow_cell(coords).in_maze
+    }
+
+    fn set_in_maze(&mut self, coords: &Coords3D, in_maze: bool) {
+        self.borrow_cell_mut(coords).in_maze = in_maze;
+    }
+
+    fn start(&self) -> Coords3D {
+        self.start_point
+    }
+
+    fn end(&self) -> Coords3D {
+        self.end_point
+    }
+}
+
+#[cfg(test)]
+mod tests_maze_3d {
+    use super::{Coords3D, Direction3D, Maze3D};
+    use crate::grid::{generate_with_seed, Grid};
+    use crate::maze::LinkType;
+    use crate::maze_generator::{
+        kruskal::KruskalGenerator, recursive_backtracker::RecursiveBacktrackerGenerator,
+        wilson::WilsonGenerator,
+    };
+    use crate::solver::solve_maze;
+
+    fn assert_perfect(maze: &Maze3D) {
+        let nodes = maze.nodes();
+        let link_count: usize = nodes.iter().map(|c| maze.links(c).len()).sum();
+        assert_eq!(link_count / 2, nodes.len() - 1);
+    }
+
+    #[test]
+    pub fn test_neighbours_are_symmetric() {
+        let maze = Maze3D::new(4, 3, 3, 0);
+        for coords in maze.nodes() {
+            for direction in maze.get_directions_possible(&coords) {
+                let neighbour = maze.get_neighbour(&coords, &direction).unwrap();
+                assert_eq!(
+                    maze.get_neighbour(&neighbour, &direction.opposite()),
+                    Some(coords)
+                );
+            }
+        }
+        assert_eq!(maze.neighbours(&Coords3D { x: 1, y: 1, z: 1 }).len(), 6);
+        assert_eq!(maze.nodes().len(), 4 * 3 * 3);
+    }
+
+    #[test]
+    pub fn test_generators_span_every_floor() {
+        let mut maze = Maze3D::new(6, 5, 3, 0);
+        generate_with_seed(&WilsonGenerator, &mut maze, 1).unwrap();
+        assert_perfect(&maze);
+
+        let mut maze = Maze3D::new(6, 5, 3, 0);
+        generate_with_seed(&KruskalGenerator, &mut maze, 2).unwrap();
+        assert_perfect(&maze);
+
+        let mut maze = Maze3D::new(6, 5, 3, 0);
+        generate_with_seed(&RecursiveBacktrackerGenerator, &mut maze, 3).unwrap();
+        assert_perfect(&maze);
+    }
+
+    #[test]
+    pub fn test_solution_climbs_to_the_top_floor() {
+        let mut maze = Maze3D::new(5, 5, 4, 0);
+        generate_with_seed(&WilsonGenerator, &mut maze, 4).unwrap();
+        let path = solve_maze(&maze).unwrap();
+        assert_eq!(path.data.first(), Some(maze.get_start_point()));
+        assert_eq!(path.data.last(), Some(maze.get_end_point()));
+        for pair in path.data.windows(2) {
+            let direction = maze.direction_to(&pair[0], &pair[1]).unwrap();
+            assert_eq!(
+                maze.borrow_cell(&pair[0]).get_link(&direction),
+                &LinkType::Path
+            );
+        }
+        assert!(path
+            .data
+            .windows(2)
+            .any(|pair| maze.direction_to(&pair[0], &pair[1]) == Some(Direction3D::Upstairs)));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::grid::{Grid, GridGenerator};
+use crate::maze::{Maze, MazeGenerator};
+use crate::maze_generator::wilson::WilsonGenerator;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::io;
+
+///Aldous-Broder: a plain random walk that carves into every cell it visits for the first time.
+///Produces a uniform spanning tree, fast at the beginning but slow to find the last cells.
+pub struct AldousBroderGenerator;
+
+impl MazeGenerator for AldousBroderGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        self.generate_grid(maze, rng)
+    }
+}
+
+impl GridGenerator for AldousBroderGenerator {
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        let total = count_nodes(grid)?;
+        random_walk_until(grid, total, rng)?;
+        Ok(())
+    }
+}
+
+///Runs Aldous-Broder until `switch_fraction` of the cells are in maze, then finishes with Wilson.
+///Both phases keep the spanning tree uniform, each one is used where it is fast.
+pub struct AldousBroderWilsonGenerator {
+    pub switch_fraction: f64,
+}
+
+impl MazeGenerator for AldousBroderWilsonGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        self.generate_grid(maze, rng)
+    }
+}
+
+impl GridGenerator for AldousBroderWilsonGenerator {
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        if !(0.0..=1.0).contains(&self.switch_fraction) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Switch fraction must be between 0 and 1",
+            ));
+        }
+        let total = count_nodes(grid)?;
+        let target = ((total as f64 * self.switch_fraction).ceil() as usize).clamp(1, total);
+        random_walk_until(grid, target, rng)?;
+        WilsonGenerator::connect_remaining(grid, rng)
+    }
+}
+
+///Number of cells of the grid, an error if there is none to start the walk from
+fn count_nodes<G: Grid>(grid: &G) -> Result<usize, io::Error> {
+    match grid.nodes().len() {
+        0 => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The grid has no cells",
+        )),
+        total => Ok(total),
+    }
+}
+
+///Random walk from a random cell until `target` cells are in maze, returns the number in maze
+fn random_walk_until<G: Grid>(
+    grid: &mut G,
+    target: usize,
+    rng: &mut dyn RngCore,
+) -> Result<usize, io::Error> {
+    let mut current = grid.random_node(rng);
+    grid.set_in_maze(&current, true);
+    let mut visited = 1;
+
+    while visited < target {
+        let neighbours = grid.neighbours(&current);
+        let next = *neighbours.choose(rng).ok_or(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "No direction available",
+        ))?;
+        if !grid.is_in_maze(&next) {
+            grid.link(&current, &next);
+            grid.set_in_maze(&next, true);
+            visited += 1;
+        }
+        current = next;
+    }
+    Ok(visited)
+}
+
+#[cfg(test)]
+mod tests_aldous_broder {
+    use super::{AldousBroderGenerator, AldousBroderWilsonGenerator};
+    use crate::grid::generate_with_seed;
+    use crate::maze::{LinkType, Maze, MazeBuilder, MazeGenerator};
+    use crate::maze_generator::tests_generator::generate_spanning_tree;
+    use std::collections::HashMap;
+
+    ///Passages of the maze, two mazes with the same key are the same spanning tree
+    fn tree_key(maze: &Maze) -> Vec<bool> {
+        maze.get_active_cells()
+            .iter()
+            .flat_map(|coords| {
+                let cell = maze.borrow_cell(coords);
+                [cell.right == LinkType::Path, cell.bottom == LinkType::Path]
+            })
+            .collect()
+    }
+
+    ///Generates many 3x2 mazes, whose grid has 15 spanning trees, and checks all come up as often
+    fn assert_uniform(generator: impl Fn() -> Box<dyn MazeGenerator>) {
+        let mut counts: HashMap<Vec<bool>, usize> = HashMap::new();
+        for seed in 0..3000 {
+            let maze = generate_spanning_tree(generator(), 3, 2, seed);
+            *counts.entry(tree_key(&maze)).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 15);
+        //200 expected each, the bounds are more than 3 standard deviations away
+        assert!(counts.values().all(|count| (150..=250).contains(count)));
+    }
+
+    #[test]
+    pub fn test_generate_perfect_maze() {
+        generate_spanning_tree(Box::new(AldousBroderGenerator), 15, 15, 1);
+    }
+
+    #[test]
+    pub fn test_spanning_trees_are_uniform() {
+        assert_uniform(|| Box::new(AldousBroderGenerator));
+        assert_uniform(|| {
+            Box::new(AldousBroderWilsonGenerator {
+                switch_fraction: 0.5,
+            })
+        });
+    }
+
+    #[test]
+    pub fn test_hybrid_generate_perfect_maze() {
+        for switch_fraction in [0.0, 0.4, 1.0] {
+            let generator = Box::new(AldousBroderWilsonGenerator { switch_fraction });
+            generate_spanning_tree(generator, 20, 10, 1);
+        }
+        //switching after the last cell leaves nothing to Wilson
+        let hybrid = AldousBroderWilsonGenerator {
+            switch_fraction: 1.0,
+        };
+        let plain = generate_spanning_tree(Box::new(AldousBroderGenerator), 12, 9, 4);
+        let full = generate_spanning_tree(Box::new(hybrid), 12, 9, 4);
+        assert_eq!(tree_key(&plain), tree_key(&full));
+    }
+
+    #[test]
+    pub fn test_hybrid_rejects_invalid_fraction() {
+        let generator = Box::new(AldousBroderWilsonGenerator {
+            switch_fraction: 1.5,
+        });
+        assert!(MazeBuilder::from_generator(generator)
+            .generate(5, 5)
+            .is_err());
+    }
+
+    #[test]
+    pub fn test_empty_grid_is_an_error() {
+        let mut maze = MazeBuilder::from_generator(Box::new(AldousBroderGenerator))
+            .generate(3, 3)
+            .unwrap();
+        for coords in maze.get_active_cells() {
+            maze.borrow_cell_mut(&coords).active = false;
+        }
+        assert!(generate_with_seed(&AldousBroderGenerator, &mut maze, 1).is_err());
+        let hybrid = AldousBroderWilsonGenerator {
+            switch_fraction: 0.5,
+        };
+        assert!(generate_with_seed(&hybrid, &mut maze, 1).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::maze::{Coords, Maze, MazeGenerator};
+use crate::maze_generator::{unsupported_mask_error, unsupported_wrap_error, Bias};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::io;
+
+///Binary tree: every cell carves towards one of the two directions of `bias`
+pub struct BinaryTreeGenerator {
+    pub bias: Bias,
+}
+
+impl MazeGenerator for BinaryTreeGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        if maze.is_masked() {
+            return Err(unsupported_mask_error());
+        }
+        if maze.is_wrapping() {
+            return Err(unsupported_wrap_error());
+        }
+        let (width, height) = maze.get_dimensions();
+        let biased = [self.bias.vertical(), self.bias.horizontal()];
+
+        for y in 0..height {
+            for x in 0..width {
+                let coords = Coords { x, y };
+                let directions: Vec<_> = maze
+                    .get_directions_possible(&coords)
+                    .into_iter()
+                    .filter(|direction| biased.contains(direction))
+                    .collect();
+                if let Some(direction) = directions.choose(rng) {
+                    maze.carve_path(&coords, direction);
+                }
+                maze.borrow_cell_mut(&coords).in_maze = true;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_binary_tree {
+    use super::BinaryTreeGenerator;
+    use crate::maze::{Coords, Direction, LinkType};
+    use crate::maze_generator::tests_generator::generate_spanning_tree;
+    use crate::maze_generator::Bias;
+
+    #[test]
+    pub fn test_every_cell_carves_towards_the_bias() {
+        for bias in [
+            Bias::NorthWest,
+            Bias::NorthEast,
+            Bias::SouthWest,
+            Bias::SouthEast,
+        ] {
+            let maze = generate_spanning_tree(Box::new(BinaryTreeGenerator { bias }), 12, 9, 3);
+            let corner = Coords {
+                x: if bias.horizontal() == Direction::Left {
+                    0
+                } else {
+                    11
+                },
+                y: if bias.vertical() == Direction::Up {
+                    0
+                } else {
+                    8
+                },
+            };
+            //no cell carves into the bias side of another, so each passage there is its own
+            for coords in maze.get_active_cells() {
+                let cell = maze.borrow_cell(&coords);
+                let towards_bias = [bias.vertical(), bias.horizontal()]
+                    .iter()
+                    .filter(|direction| *cell.get_link(direction) == LinkType::Path)
+                    .count();
+                assert_eq!(towards_bias, if coords == corner { 0 } else { 1 });
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::maze::{Cell, Coords, LinkType, Maze, MazeGenerator};
+use crate::maze_generator::{unsupported_mask_error, unsupported_wrap_error};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand::RngCore;
+use std::io;
+
+///Eller's algorithm: builds the maze one row at a time,
+///only the set membership of the current row is kept in memory.
+pub struct EllerGenerator;
+
+impl MazeGenerator for EllerGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        if maze.is_masked() {
+            return Err(unsupported_mask_error());
+        }
+        if maze.is_wrapping() {
+            return Err(unsupported_wrap_error());
+        }
+        let (width, height) = maze.get_dimensions();
+        let rows = EllerRows::new(width, Some(height), rng);
+        for (y, row) in rows.enumerate() {
+            for (x, cell) in row.into_iter().enumerate() {
+                *maze.borrow_cell_mut(&Coords { x, y }) = cell;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl EllerGenerator {
+    ///Endless stream of rows drawn from `rng`. Every set of cells reaches down into the next row,
+    ///but separate sets are only joined by a closing row, which never comes: a prefix of the
+    ///stream has no loops but can be made of several disconnected parts.
+    pub fn rows<R: Rng>(width: usize, rng: R) -> EllerRows<R> {
+        EllerRows::new(width, None, rng)
+    }
+
+    ///Stream of exactly `height` rows drawn from `rng`,
+    ///the last one joins all remaining sets into a perfect maze
+    pub fn rows_with_height<R: Rng>(width: usize, height: usize, rng: R) -> EllerRows<R> {
+        EllerRows::new(width, Some(height), rng)
+    }
+}
+
+///Iterator yielding the rows of an Eller maze from top to bottom
+pub struct EllerRows<R: Rng> {
+    width: usize,
+    remaining: Option<usize>,
+    sets: Vec<Option<usize>>,
+    next_set: usize,
+    rng: R,
+}
+
+impl<R: Rng> EllerRows<R> {
+    pub fn new(width: usize, height: Option<usize>, rng: R) -> EllerRows<R> {
+        EllerRows {
+            width,
+            remaining: height,
+            sets: vec![None; width],
+            next_set: 0,
+            rng,
+        }
+    }
+
+    fn merge_sets(sets: &mut [usize], from: usize, into: usize) {
+        for set in sets.iter_mut().filter(|set| **set == from) {
+            *set = into;
+        }
+    }
+}
+
+impl<R: Rng> Iterator for EllerRows<R> {
+    type Item = Vec<Cell>;
+
+    fn next(&mut self) -> Option<Vec<Cell>> {
+        if self.width == 0 || self.remaining == Some(0) {
+            return None;
+        }
+        let last_row = self.remaining == Some(1);
+        self.remaining = self.remaining.map(|remaining| remaining - 1);
+
+        let mut row: Vec<Cell> = Vec::with_capacity(self.width);
+        let mut sets: Vec<usize> = Vec::with_capacity(self.width);
+        for set in self.sets.iter() {
+            let mut cell = Cell::walled();
+            cell.in_maze = true;
+            match set {
+                Some(set) => {
+                    cell.top = LinkType::Path;
+                    sets.push(*set);
+                }
+                None => {
+                    sets.push(self.next_set);
+                    self.next_set += 1;
+                }
+            }
+            row.push(cell);
+        }
+
+        for x in 0..self.width - 1 {
+            if sets[x] != sets[x + 1] && (last_row || self.rng.gen_bool(0.5)) {
+                row[x].right = LinkType::Path;
+                row[x + 1].left = LinkType::Path;
+                let (from, into) = (sets[x + 1], sets[x]);
+                EllerRows::<R>::merge_sets(&mut sets, from, into);
+            }
+        }
+
+        self.sets = vec![None; self.width];
+        if last_row {
+            return Some(row);
+        }
+
+        let mut indices: Vec<usize> = (0..self.width).collect();
+        indices.shuffle(&mut self.rng);
+        indices.sort_by_key(|x| sets[*x]);
+        for group in indices.chunk_by(|a, b| sets[*a] == sets[*b]) {
+            for (i, x) in group.iter().enumerate() {
+                if i == 0 || self.rng.gen_bool(0.5) {
+                    row[*x].bottom = LinkType::Path;
+                    self.sets[*x] = Some(sets[*x]);
+                }
+            }
+        }
+        Some(row)
+    }
+}
+
+#[cfg(test)]
+mod tests_eller {
+    use super::EllerGenerator;
+    use crate::disjoint_set::DisjointSet;
+    use crate::maze::{Coords, LinkType};
+    use crate::maze_generator::tests_generator::generate_spanning_tree;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    pub fn test_generate_perfect_maze() {
+        generate_spanning_tree(Box::new(EllerGenerator), 30, 12, 1);
+    }
+
+    #[test]
+    pub fn test_maze_is_the_row_stream() {
+        let maze = generate_spanning_tree(Box::new(EllerGenerator), 9, 7, 5);
+        let rows = EllerGenerator::rows_with_height(9, 7, ChaCha8Rng::seed_from_u64(5));
+        for (y, row) in rows.enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                assert_eq!(maze.borrow_cell(&Coords { x, y }), cell);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_endless_rows_stay_linked() {
+        let mut previous: Option<Vec<crate::maze::Cell>> = None;
+        let mut sets = DisjointSet::new(8 * 1000);
+        let rows = EllerGenerator::rows(8, ChaCha8Rng::seed_from_u64(1)).take(1000);
+        for (y, row) in rows.enumerate() {
+            assert_eq!(row.len(), 8);
+            if let Some(previous) = previous {
+                for (above, cell) in previous.iter().zip(row.iter()) {
+                    assert_eq!(above.bottom == LinkType::Path, cell.top == LinkType::Path);
+                }
+            }
+            //no passage closes a loop, every prefix of the stream is a forest
+            for (x, cell) in row.iter().enumerate() {
+                let index = y * 8 + x;
+                if cell.top == LinkType::Path {
+                    assert!(sets.union(index, index - 8));
+                }
+                if cell.right == LinkType::Path {
+                    assert!(sets.union(index, index + 1));
+                }
+            }
+            previous = Some(row);
+        }
+    }
+
+    #[test]
+    pub fn test_seeded_rows_are_reproducible() {
+        let rows = |seed| {
+            EllerGenerator::rows_with_height(10, 6, ChaCha8Rng::seed_from_u64(seed))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rows(3), rows(3));
+        assert_eq!(rows(3).len(), 6);
+        assert_ne!(rows(3), rows(4));
+    }
+}
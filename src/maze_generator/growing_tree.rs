@@ -0,0 +1,250 @@
+use crate::grid::{Grid, GridGenerator};
+use crate::maze::{Maze, MazeGenerator};
+use crate::maze_generator::random_index;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::collections::VecDeque;
+use std::io;
+use std::str::FromStr;
+
+///Picks which active cell the growing tree extends next.
+///Always newest gives backtracker-like mazes, always random gives Prim-like ones.
+pub trait CellSelector {
+    ///Index into the active list of `len` cells, ordered from oldest to newest
+    fn select(&self, len: usize, rng: &mut dyn RngCore) -> usize;
+}
+
+pub struct NewestSelector;
+
+impl CellSelector for NewestSelector {
+    fn select(&self, len: usize, _rng: &mut dyn RngCore) -> usize {
+        len - 1
+    }
+}
+
+pub struct OldestSelector;
+
+impl CellSelector for OldestSelector {
+    fn select(&self, _len: usize, _rng: &mut dyn RngCore) -> usize {
+        0
+    }
+}
+
+pub struct RandomSelector;
+
+impl CellSelector for RandomSelector {
+    fn select(&self, len: usize, rng: &mut dyn RngCore) -> usize {
+        random_index(rng, len)
+    }
+}
+
+///Delegates each choice to one of its selectors, picked proportionally to its weight
+pub struct WeightedSelector {
+    selectors: Vec<(Box<dyn CellSelector>, u32)>,
+    total_weight: u32,
+}
+
+impl WeightedSelector {
+    ///Panics if the weights add up to more than `u32::MAX`, [`SelectorSpec`] rejects such specs
+    pub fn new(selectors: Vec<(Box<dyn CellSelector>, u32)>) -> WeightedSelector {
+        let total_weight = selectors
+            .iter()
+            .try_fold(0u32, |total, (_, weight)| total.checked_add(*weight))
+            .expect("Selector weights add up to more than u32::MAX");
+        WeightedSelector {
+            selectors,
+            total_weight,
+        }
+    }
+}
+
+impl CellSelector for WeightedSelector {
+    fn select(&self, len: usize, rng: &mut dyn RngCore) -> usize {
+        let mut roll = rng.gen_range(0..self.total_weight.max(1));
+        for (selector, weight) in self.selectors.iter() {
+            if roll < *weight {
+                return selector.select(len, rng);
+            }
+            roll -= weight;
+        }
+        len - 1
+    }
+}
+
+pub struct GrowingTreeGenerator {
+    pub selector: Box<dyn CellSelector>,
+}
+
+impl MazeGenerator for GrowingTreeGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        self.generate_grid(maze, rng)
+    }
+}
+
+impl GridGenerator for GrowingTreeGenerator {
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        let start = grid.random_node(rng);
+        grid.set_in_maze(&start, true);
+        let mut active: VecDeque<G::Node> = VecDeque::from([start]);
+
+        while !active.is_empty() {
+            let index = self.selector.select(active.len(), rng);
+            let node = active[index];
+            let neighbours = grid.unvisited_neighbours(&node);
+            match neighbours.choose(rng) {
+                Some(next) => {
+                    grid.link(&node, next);
+                    grid.set_in_maze(next, true);
+                    active.push_back(*next);
+                }
+                None => {
+                    active.remove(index);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectorKind {
+    Newest,
+    Oldest,
+    Random,
+}
+
+///Parsed form of the built-in selectors, e.g. `newest` or `newest:75,random:25`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectorSpec {
+    weights: Vec<(SelectorKind, u32)>,
+}
+
+impl SelectorSpec {
+    pub fn build(&self) -> Box<dyn CellSelector> {
+        if let [(kind, _)] = self.weights[..] {
+            return SelectorSpec::build_kind(kind);
+        }
+        let selectors = self
+            .weights
+            .iter()
+            .map(|(kind, weight)| (SelectorSpec::build_kind(*kind), *weight))
+            .collect();
+        Box::new(WeightedSelector::new(selectors))
+    }
+
+    fn build_kind(kind: SelectorKind) -> Box<dyn CellSelector> {
+        match kind {
+            SelectorKind::Newest => Box::new(NewestSelector),
+            SelectorKind::Oldest => Box::new(OldestSelector),
+            SelectorKind::Random => Box::new(RandomSelector),
+        }
+    }
+}
+
+impl FromStr for SelectorSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<SelectorSpec, String> {
+        let mut weights = Vec::new();
+        for part in s.split(',') {
+            let (name, weight) = match part.split_once(':') {
+                Some((name, weight)) => {
+                    let weight = weight
+                        .trim()
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid weight `{weight}`"))?;
+                    (name, weight)
+                }
+                None => (part, 1),
+            };
+            let kind = match name.trim() {
+                "newest" => SelectorKind::Newest,
+                "oldest" => SelectorKind::Oldest,
+                "random" => SelectorKind::Random,
+                other => return Err(format!("Unknown selector `{other}`")),
+            };
+            weights.push((kind, weight));
+        }
+
+        let total = weights
+            .iter()
+            .try_fold(0u32, |total, (_, weight)| total.checked_add(*weight))
+            .ok_or_else(|| format!("Weights can't add up to more than {}", u32::MAX))?;
+        if total == 0 {
+            return Err("At least one selector needs a positive weight".to_string());
+        }
+        Ok(SelectorSpec { weights })
+    }
+}
+
+#[cfg(test)]
+mod tests_growing_tree {
+    use super::{
+        CellSelector, GrowingTreeGenerator, NewestSelector, OldestSelector, SelectorKind,
+        SelectorSpec,
+    };
+    use crate::maze::MazeBuilder;
+    use crate::maze_generator::tests_generator::{dead_end_share, generate_spanning_tree};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn generator(spec: &str) -> Box<GrowingTreeGenerator> {
+        let selector = spec.parse::<SelectorSpec>().unwrap().build();
+        Box::new(GrowingTreeGenerator { selector })
+    }
+
+    #[test]
+    pub fn test_generate_perfect_maze_for_every_selector() {
+        for spec in ["newest", "oldest", "random", "newest:75,random:25"] {
+            generate_spanning_tree(generator(spec), 16, 12, 1);
+        }
+    }
+
+    #[test]
+    pub fn test_selectors_pick_by_age() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        assert_eq!(NewestSelector.select(5, &mut rng), 4);
+        assert_eq!(OldestSelector.select(5, &mut rng), 0);
+        //a selector without weight is never asked
+        let selector = "oldest:0,newest:3".parse::<SelectorSpec>().unwrap().build();
+        assert!((0..100).all(|_| selector.select(7, &mut rng) == 6));
+        let selector = "oldest:3,newest:0".parse::<SelectorSpec>().unwrap().build();
+        assert!((0..100).all(|_| selector.select(7, &mut rng) == 0));
+    }
+
+    #[test]
+    pub fn test_newest_carves_corridors_random_carves_branches() {
+        //newest is a depth-first walk, random grows the maze evenly like Prim
+        for seed in 0..3 {
+            let newest = generate_spanning_tree(generator("newest"), 30, 30, seed);
+            let random = generate_spanning_tree(generator("random"), 30, 30, seed);
+            assert!(dead_end_share(&random) > 2.0 * dead_end_share(&newest));
+        }
+    }
+
+    #[test]
+    pub fn test_parse_selector_spec() {
+        let spec: SelectorSpec = "newest:75, random:25".parse().unwrap();
+        assert_eq!(
+            spec.weights,
+            vec![(SelectorKind::Newest, 75), (SelectorKind::Random, 25)]
+        );
+        assert!("newest:x".parse::<SelectorSpec>().is_err());
+        assert!("latest".parse::<SelectorSpec>().is_err());
+        assert!("oldest:0".parse::<SelectorSpec>().is_err());
+    }
+
+    #[test]
+    pub fn test_reject_weights_overflowing_u32() {
+        assert!("newest:4294967295,random:1"
+            .parse::<SelectorSpec>()
+            .is_err());
+        let spec: SelectorSpec = "newest:4294967294,random:1".parse().unwrap();
+        let generator = Box::new(GrowingTreeGenerator {
+            selector: spec.build(),
+        });
+        assert!(MazeBuilder::from_generator(generator)
+            .generate(6, 6)
+            .is_ok());
+    }
+}
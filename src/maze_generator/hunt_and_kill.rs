@@ -0,0 +1,112 @@
+use crate::grid::{Grid, GridGenerator};
+use crate::maze::{Maze, MazeGenerator};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::io;
+
+///Hunt-and-kill: random walk into unvisited cells, when stuck scan the rows
+///for an unvisited cell next to the maze and continue walking from there
+pub struct HuntAndKillGenerator;
+
+impl MazeGenerator for HuntAndKillGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        self.generate_grid(maze, rng)
+    }
+}
+
+impl GridGenerator for HuntAndKillGenerator {
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        let nodes = grid.nodes();
+        let mut current = Some(grid.random_node(rng));
+        let mut hunt_start = 0;
+
+        while let Some(node) = current {
+            grid.set_in_maze(&node, true);
+            let neighbours = grid.unvisited_neighbours(&node);
+            current = match neighbours.choose(rng) {
+                Some(next) => {
+                    grid.link(&node, next);
+                    Some(*next)
+                }
+                None => HuntAndKillGenerator::hunt(grid, &nodes, &mut hunt_start, rng),
+            };
+        }
+        Ok(())
+    }
+}
+
+impl HuntAndKillGenerator {
+    ///Finds the first unvisited node next to the maze, links it to the maze and returns it.
+    ///Nodes before `hunt_start` are known to be in maze and are skipped.
+    fn hunt<G: Grid>(
+        grid: &mut G,
+        nodes: &[G::Node],
+        hunt_start: &mut usize,
+        rng: &mut dyn RngCore,
+    ) -> Option<G::Node> {
+        while *hunt_start < nodes.len() && grid.is_in_maze(&nodes[*hunt_start]) {
+            *hunt_start += 1;
+        }
+
+        for node in nodes[*hunt_start..].iter() {
+            if grid.is_in_maze(node) {
+                continue;
+            }
+            let in_maze: Vec<_> = grid
+                .neighbours(node)
+                .into_iter()
+                .filter(|neighbour| grid.is_in_maze(neighbour))
+                .collect();
+            if let Some(neighbour) = in_maze.choose(rng) {
+                grid.link(node, neighbour);
+                return Some(*node);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests_hunt_and_kill {
+    use super::HuntAndKillGenerator;
+    use crate::grid::Grid;
+    use crate::maze::Coords;
+    use crate::maze_generator::tests_generator::{generate_spanning_tree, walled_maze};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    pub fn test_generate_perfect_maze() {
+        generate_spanning_tree(Box::new(HuntAndKillGenerator), 21, 14, 1);
+    }
+
+    #[test]
+    pub fn test_hunt_takes_the_first_cell_next_to_the_maze() {
+        let mut maze = walled_maze(4, 3);
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let nodes = maze.nodes();
+        for coords in [
+            Coords { x: 0, y: 0 },
+            Coords { x: 1, y: 0 },
+            Coords { x: 1, y: 1 },
+        ] {
+            maze.set_in_maze(&coords, true);
+        }
+        let mut hunt_start = 0;
+
+        //(2, 0) comes before (0, 1) in row order, both touch the maze
+        let found = HuntAndKillGenerator::hunt(&mut maze, &nodes, &mut hunt_start, &mut rng);
+        assert_eq!(found, Some(Coords { x: 2, y: 0 }));
+        assert_eq!(
+            maze.links(&Coords { x: 2, y: 0 }),
+            vec![Coords { x: 1, y: 0 }]
+        );
+        //the cells in maze at the front are skipped next time
+        assert_eq!(hunt_start, 2);
+
+        //the hunt does not put the cell in maze, the walk from it does
+        maze.set_in_maze(&Coords { x: 2, y: 0 }, true);
+        let found = HuntAndKillGenerator::hunt(&mut maze, &nodes, &mut hunt_start, &mut rng);
+        assert_eq!(found, Some(Coords { x: 3, y: 0 }));
+    }
+}
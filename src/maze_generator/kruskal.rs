@@ -0,0 +1,81 @@
+use crate::disjoint_set::DisjointSet;
+use crate::grid::{Grid, GridGenerator};
+use crate::maze::{Maze, MazeGenerator};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::collections::HashMap;
+use std::io;
+
+///Randomized Kruskal: knocks down shuffled walls between cells of different sets
+pub struct KruskalGenerator;
+
+impl MazeGenerator for KruskalGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        self.generate_grid(maze, rng)
+    }
+}
+
+impl GridGenerator for KruskalGenerator {
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        let nodes = grid.nodes();
+        let indices: HashMap<G::Node, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (*node, index))
+            .collect();
+
+        //every wall once, from the node that comes first
+        let mut walls: Vec<(G::Node, G::Node)> = Vec::with_capacity(2 * nodes.len());
+        for (index, node) in nodes.iter().enumerate() {
+            for neighbour in grid.neighbours(node) {
+                if indices[&neighbour] > index {
+                    walls.push((*node, neighbour));
+                }
+            }
+        }
+        walls.shuffle(rng);
+
+        let mut sets = DisjointSet::new(nodes.len());
+        for (a, b) in walls {
+            if sets.union(indices[&a], indices[&b]) {
+                grid.link(&a, &b);
+                if sets.set_count() == 1 {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_kruskal {
+    use super::KruskalGenerator;
+    use crate::grid::generate_with_seed;
+    use crate::maze::Coords;
+    use crate::maze_generator::tests_generator::{generate_spanning_tree, walled_maze};
+    use crate::solver::DistanceMap;
+
+    #[test]
+    pub fn test_generate_perfect_maze() {
+        generate_spanning_tree(Box::new(KruskalGenerator), 25, 10, 1);
+    }
+
+    #[test]
+    pub fn test_split_grid_gives_one_tree_per_part() {
+        //the union-find never needs a path between the parts, unlike the walking generators
+        let mut maze = walled_maze(7, 5);
+        for y in 0..5 {
+            maze.borrow_cell_mut(&Coords { x: 3, y }).active = false;
+        }
+        generate_with_seed(&KruskalGenerator, &mut maze, 2).unwrap();
+        assert_eq!(maze.count_passages(), maze.count_active() - 2);
+        for source in [Coords { x: 0, y: 0 }, Coords { x: 6, y: 0 }] {
+            let distances = DistanceMap::new(&maze, &source);
+            for coords in maze.get_active_cells() {
+                let same_part = (coords.x < 3) == (source.x < 3);
+                assert_eq!(distances.get(&coords).is_some(), same_part);
+            }
+        }
+    }
+}
@@ -1 +1,127 @@
+use crate::maze::Direction;
+use clap::ValueEnum;
+use rand::{Rng, RngCore};
+use std::io;
+
+pub mod aldous_broder;
+pub mod binary_tree;
+pub mod eller;
+pub mod growing_tree;
+pub mod hunt_and_kill;
+pub mod kruskal;
+pub mod prim;
+pub mod recursive_backtracker;
+pub mod recursive_division;
+pub mod sidewinder;
+pub mod weave;
 pub mod wilson;
+
+///Uniform index in `0..len` drawn through a u64 so it does not depend on the width of usize,
+///which keeps seeded mazes identical across platforms
+pub(crate) fn random_index(rng: &mut dyn RngCore, len: usize) -> usize {
+    rng.gen_range(0..len as u64) as usize
+}
+
+///Error for the generators that only work on the full rectangular grid,
+///rooms are taken out of the grid during generation like masked cells
+pub(crate) fn unsupported_mask_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "This generator does not support masks or rooms",
+    )
+}
+
+///Error for the generators that rely on the outer border of the grid
+pub(crate) fn unsupported_wrap_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "This generator does not support wrapping edges",
+    )
+}
+
+///Corner the biased generators lean towards,
+///the two sides touching it end up as long uninterrupted corridors
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Bias {
+    NorthWest,
+    NorthEast,
+    SouthWest,
+    SouthEast,
+}
+
+impl Bias {
+    pub fn vertical(&self) -> Direction {
+        match self {
+            Bias::NorthWest | Bias::NorthEast => Direction::Up,
+            Bias::SouthWest | Bias::SouthEast => Direction::Down,
+        }
+    }
+
+    pub fn horizontal(&self) -> Direction {
+        match self {
+            Bias::NorthWest | Bias::SouthWest => Direction::Left,
+            Bias::NorthEast | Bias::SouthEast => Direction::Right,
+        }
+    }
+}
+
+///Shared checks of the generator tests
+#[cfg(test)]
+pub(crate) mod tests_generator {
+    use crate::grid::Grid;
+    use crate::maze::{Maze, MazeBuilder, MazeGenerator};
+    use crate::solver::DistanceMap;
+    use rand::RngCore;
+    use std::io;
+
+    ///Leaves every wall standing, for tests that run a generator on a grid they prepared
+    struct Walled;
+
+    impl MazeGenerator for Walled {
+        fn generate(&self, _maze: &mut Maze, _rng: &mut dyn RngCore) -> Result<(), io::Error> {
+            Ok(())
+        }
+    }
+
+    pub fn walled_maze(width: usize, height: usize) -> Maze {
+        MazeBuilder::from_generator(Box::new(Walled))
+            .generate(width, height)
+            .unwrap()
+    }
+
+    ///Generates a `width` by `height` maze from `seed` and checks it is a spanning tree:
+    ///links go both ways, every cell is reachable from the start and there is one passage
+    ///less than cells
+    pub fn generate_spanning_tree(
+        generator: Box<dyn MazeGenerator>,
+        width: usize,
+        height: usize,
+        seed: u64,
+    ) -> Maze {
+        let maze = MazeBuilder::from_generator(generator)
+            .with_seed(seed)
+            .generate(width, height)
+            .unwrap();
+        let nodes = maze.nodes();
+        let distances = DistanceMap::new(&maze, maze.get_start_point());
+        assert!(nodes.iter().all(|coords| distances.get(coords).is_some()));
+        for coords in nodes.iter() {
+            for neighbour in maze.links(coords) {
+                assert!(maze.links(&neighbour).contains(coords));
+            }
+        }
+        let link_count: usize = nodes.iter().map(|coords| maze.links(coords).len()).sum();
+        assert_eq!(link_count / 2, nodes.len() - 1);
+        maze
+    }
+
+    ///Share of the cells with a single passage, the texture that tells generators apart
+    pub fn dead_end_share(maze: &Maze) -> f64 {
+        let nodes = maze.nodes();
+        let dead_ends = nodes
+            .iter()
+            .filter(|coords| maze.links(coords).len() == 1)
+            .count();
+        dead_ends as f64 / nodes.len() as f64
+    }
+}
@@ -0,0 +1,83 @@
+use crate::grid::{Grid, GridGenerator};
+use crate::maze::{Maze, MazeGenerator};
+use crate::maze_generator::random_index;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::collections::HashSet;
+use std::io;
+
+///Randomized Prim: grows the maze from a seed cell by attaching random frontier cells
+pub struct PrimGenerator;
+
+impl MazeGenerator for PrimGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        self.generate_grid(maze, rng)
+    }
+}
+
+impl GridGenerator for PrimGenerator {
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        let mut in_frontier: HashSet<G::Node> = HashSet::new();
+        let mut frontier: Vec<G::Node> = Vec::new();
+
+        let seed_node = grid.random_node(rng);
+        PrimGenerator::add_to_maze(grid, &seed_node, &mut frontier, &mut in_frontier);
+
+        while !frontier.is_empty() {
+            let node = frontier.swap_remove(random_index(rng, frontier.len()));
+            let in_maze: Vec<_> = grid
+                .neighbours(&node)
+                .into_iter()
+                .filter(|neighbour| grid.is_in_maze(neighbour))
+                .collect();
+            let neighbour = in_maze.choose(rng).ok_or(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Frontier cell has no neighbour in maze",
+            ))?;
+
+            grid.link(&node, neighbour);
+            PrimGenerator::add_to_maze(grid, &node, &mut frontier, &mut in_frontier);
+        }
+        Ok(())
+    }
+}
+
+impl PrimGenerator {
+    fn add_to_maze<G: Grid>(
+        grid: &mut G,
+        node: &G::Node,
+        frontier: &mut Vec<G::Node>,
+        in_frontier: &mut HashSet<G::Node>,
+    ) {
+        grid.set_in_maze(node, true);
+        for neighbour in grid.unvisited_neighbours(node) {
+            if in_frontier.insert(neighbour) {
+                frontier.push(neighbour);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_prim {
+    use super::PrimGenerator;
+    use crate::maze_generator::recursive_backtracker::RecursiveBacktrackerGenerator;
+    use crate::maze_generator::tests_generator::{dead_end_share, generate_spanning_tree};
+
+    #[test]
+    pub fn test_generate_perfect_maze() {
+        generate_spanning_tree(Box::new(PrimGenerator), 17, 23, 1);
+    }
+
+    #[test]
+    pub fn test_random_frontier_leaves_many_short_dead_ends() {
+        //picking any frontier cell grows the maze evenly from the seed, a depth-first walk
+        //makes long corridors instead
+        for seed in 0..5 {
+            let prim = generate_spanning_tree(Box::new(PrimGenerator), 30, 30, seed);
+            let backtracker =
+                generate_spanning_tree(Box::new(RecursiveBacktrackerGenerator), 30, 30, seed);
+            assert!(dead_end_share(&prim) > 2.0 * dead_end_share(&backtracker));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::grid::{Grid, GridGenerator};
+use crate::maze::{Maze, MazeGenerator};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::io;
+
+///Depth-first search carving long, winding corridors.
+///Uses an explicit stack instead of recursion so big mazes don't overflow the call stack.
+pub struct RecursiveBacktrackerGenerator;
+
+impl MazeGenerator for RecursiveBacktrackerGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        self.generate_grid(maze, rng)
+    }
+}
+
+impl GridGenerator for RecursiveBacktrackerGenerator {
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        let start = grid.start();
+        grid.set_in_maze(&start, true);
+        let mut stack = vec![start];
+
+        while let Some(current) = stack.last().copied() {
+            let neighbours = grid.unvisited_neighbours(&current);
+            match neighbours.choose(rng) {
+                Some(next) => {
+                    grid.link(&current, next);
+                    grid.set_in_maze(next, true);
+                    stack.push(*next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_recursive_backtracker {
+    use super::RecursiveBacktrackerGenerator;
+    use crate::maze::MazeBuilder;
+    use crate::maze_generator::tests_generator::{dead_end_share, generate_spanning_tree};
+
+    #[test]
+    pub fn test_depth_first_walk_leaves_few_dead_ends() {
+        //it only turns back once stuck, around a tenth of the cells end up as dead ends
+        for seed in 0..5 {
+            let maze =
+                generate_spanning_tree(Box::new(RecursiveBacktrackerGenerator), 20, 15, seed);
+            assert!(dead_end_share(&maze) < 0.15);
+        }
+    }
+
+    #[test]
+    pub fn test_generate_large_maze_without_overflow() {
+        let generator = Box::new(RecursiveBacktrackerGenerator);
+        let maze = MazeBuilder::from_generator(generator)
+            .generate(1000, 1000)
+            .unwrap();
+        assert_eq!(maze.count_passages(), 1000 * 1000 - 1);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::maze::{Coords, LinkType, Maze, MazeGenerator};
+use crate::maze_generator::{random_index, unsupported_mask_error, unsupported_wrap_error};
+use rand::{Rng, RngCore};
+use std::io;
+
+///Recursive division: starts from an open field and splits it with walls
+///that have a single gap, giving long straight rectilinear walls.
+pub struct RecursiveDivisionGenerator;
+
+///Rectangle of cells still to be divided
+struct Chamber {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl MazeGenerator for RecursiveDivisionGenerator {
+    fn initial_link(&self) -> LinkType {
+        LinkType::Path
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        if maze.is_masked() {
+            return Err(unsupported_mask_error());
+        }
+        if maze.is_wrapping() {
+            return Err(unsupported_wrap_error());
+        }
+        let (width, height) = maze.get_dimensions();
+        for y in 0..height {
+            for x in 0..width {
+                maze.borrow_cell_mut(&Coords { x, y }).in_maze = true;
+            }
+        }
+
+        let mut chambers = vec![Chamber {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }];
+        while let Some(chamber) = chambers.pop() {
+            if chamber.width < 2 && chamber.height < 2 {
+                continue;
+            }
+            let horizontal = if chamber.width == chamber.height {
+                rng.gen_bool(0.5)
+            } else {
+                chamber.height > chamber.width
+            };
+
+            if horizontal {
+                let wall_y = chamber.y + random_index(rng, chamber.height - 1);
+                let gap_x = chamber.x + random_index(rng, chamber.width);
+                for x in (chamber.x..chamber.x + chamber.width).filter(|x| *x != gap_x) {
+                    maze.borrow_cell_mut(&Coords { x, y: wall_y }).bottom = LinkType::Wall;
+                    maze.borrow_cell_mut(&Coords { x, y: wall_y + 1 }).top = LinkType::Wall;
+                }
+                chambers.push(Chamber {
+                    height: wall_y - chamber.y + 1,
+                    ..chamber
+                });
+                chambers.push(Chamber {
+                    y: wall_y + 1,
+                    height: chamber.y + chamber.height - wall_y - 1,
+                    ..chamber
+                });
+            } else {
+                let wall_x = chamber.x + random_index(rng, chamber.width - 1);
+                let gap_y = chamber.y + random_index(rng, chamber.height);
+                for y in (chamber.y..chamber.y + chamber.height).filter(|y| *y != gap_y) {
+                    maze.borrow_cell_mut(&Coords { x: wall_x, y }).right = LinkType::Wall;
+                    maze.borrow_cell_mut(&Coords { x: wall_x + 1, y }).left = LinkType::Wall;
+                }
+                chambers.push(Chamber {
+                    width: wall_x - chamber.x + 1,
+                    ..chamber
+                });
+                chambers.push(Chamber {
+                    x: wall_x + 1,
+                    width: chamber.x + chamber.width - wall_x - 1,
+                    ..chamber
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_recursive_division {
+    use super::RecursiveDivisionGenerator;
+    use crate::maze::{Coords, LinkType, MazeBuilder};
+    use crate::maze_generator::tests_generator::generate_spanning_tree;
+
+    #[test]
+    pub fn test_first_wall_crosses_the_whole_maze_with_one_gap() {
+        //taller than wide, so the first wall is horizontal, later walls never open it again
+        for seed in 0..5 {
+            let maze = generate_spanning_tree(Box::new(RecursiveDivisionGenerator), 19, 27, seed);
+            let gaps = |y: usize| {
+                (0..19)
+                    .filter(|x| maze.borrow_cell(&Coords { x: *x, y }).bottom == LinkType::Path)
+                    .count()
+            };
+            assert!((0..26).any(|y| gaps(y) == 1));
+        }
+    }
+
+    #[test]
+    pub fn test_border_stays_closed() {
+        let generator = Box::new(RecursiveDivisionGenerator);
+        let maze = MazeBuilder::from_generator(generator)
+            .generate(8, 6)
+            .unwrap();
+        for x in 0..8 {
+            assert_eq!(maze.borrow_cell(&Coords { x, y: 0 }).top, LinkType::Wall);
+            assert_eq!(maze.borrow_cell(&Coords { x, y: 5 }).bottom, LinkType::Wall);
+        }
+        for y in 0..6 {
+            assert_eq!(maze.borrow_cell(&Coords { x: 0, y }).left, LinkType::Wall);
+            assert_eq!(maze.borrow_cell(&Coords { x: 7, y }).right, LinkType::Wall);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::maze::{Coords, Direction, Maze, MazeGenerator};
+use crate::maze_generator::{unsupported_mask_error, unsupported_wrap_error, Bias};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::io;
+
+///Sidewinder: rows are split into horizontal runs and each run carves
+///once towards the vertical side of `bias`. The row on that side is one long corridor.
+pub struct SidewinderGenerator {
+    pub bias: Bias,
+}
+
+impl MazeGenerator for SidewinderGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        if maze.is_masked() {
+            return Err(unsupported_mask_error());
+        }
+        if maze.is_wrapping() {
+            return Err(unsupported_wrap_error());
+        }
+        let (width, height) = maze.get_dimensions();
+        let vertical = self.bias.vertical();
+        let horizontal = self.bias.horizontal();
+
+        for y in 0..height {
+            let mut run: Vec<Coords> = Vec::new();
+            for i in 0..width {
+                let x = if horizontal == Direction::Right {
+                    i
+                } else {
+                    width - 1 - i
+                };
+                let coords = Coords { x, y };
+                maze.borrow_cell_mut(&coords).in_maze = true;
+                run.push(coords);
+
+                let directions = maze.get_directions_possible(&coords);
+                let can_continue = directions.contains(&horizontal);
+                let can_close = directions.contains(&vertical);
+
+                if can_close && (!can_continue || rng.gen_bool(0.5)) {
+                    let member = run.choose(rng).unwrap();
+                    maze.carve_path(member, &vertical);
+                    run.clear();
+                } else if can_continue {
+                    maze.carve_path(&coords, &horizontal);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_sidewinder {
+    use super::SidewinderGenerator;
+    use crate::maze::{Coords, Direction, LinkType};
+    use crate::maze_generator::tests_generator::generate_spanning_tree;
+    use crate::maze_generator::Bias;
+
+    #[test]
+    pub fn test_every_run_carves_once_towards_the_bias() {
+        for bias in [
+            Bias::NorthWest,
+            Bias::NorthEast,
+            Bias::SouthWest,
+            Bias::SouthEast,
+        ] {
+            let maze = generate_spanning_tree(Box::new(SidewinderGenerator { bias }), 11, 13, 3);
+            let vertical = bias.vertical();
+            let corridor = if vertical == Direction::Up { 0 } else { 12 };
+            for y in 0..13 {
+                let link = |x: usize, direction: &Direction| {
+                    *maze.borrow_cell(&Coords { x, y }).get_link(direction) == LinkType::Path
+                };
+                if y == corridor {
+                    assert!((0..10).all(|x| link(x, &Direction::Right)));
+                    continue;
+                }
+                //a run ends at the first wall to the right
+                let mut carved = 0;
+                for x in 0..11 {
+                    carved += link(x, &vertical) as usize;
+                    if !link(x, &Direction::Right) {
+                        assert_eq!(carved, 1);
+                        carved = 0;
+                    }
+                }
+            }
+        }
+    }
+}
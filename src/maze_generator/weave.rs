@@ -0,0 +1,165 @@
+use crate::disjoint_set::DisjointSet;
+use crate::maze::{Coords, Crossing, Direction, LinkType, Maze, MazeGenerator};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
+use std::io;
+
+///Kruskal with over/under crossings: before the walls are knocked down, some cells are turned
+///into crossings with a passage straight over them and a tunnel under it. Both passages join
+///sets like any other wall, so the result is still a perfect maze.
+pub struct WeaveGenerator {
+    ///Chance (0.0-1.0) for each eligible cell to become a crossing
+    pub crossing_probability: f64,
+}
+
+impl MazeGenerator for WeaveGenerator {
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        if !(0.0..=1.0).contains(&self.crossing_probability) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Crossing probability must be between 0 and 1",
+            ));
+        }
+
+        let cells = maze.get_active_cells();
+        let indices: HashMap<Coords, usize> = cells
+            .iter()
+            .enumerate()
+            .map(|(index, coords)| (*coords, index))
+            .collect();
+        let mut sets = DisjointSet::new(cells.len());
+
+        for coords in cells.iter() {
+            if rng.gen_bool(self.crossing_probability) {
+                WeaveGenerator::add_crossing(maze, coords, &indices, &mut sets, rng);
+            }
+        }
+
+        //plain Kruskal over the walls left, crossing cells have no wall left to knock down
+        let mut walls: Vec<(Coords, Direction)> = Vec::new();
+        for coords in cells.iter() {
+            if maze.borrow_cell(coords).crossing != Crossing::None {
+                continue;
+            }
+            for direction in maze.get_directions_possible(coords) {
+                let neighbour = maze.get_neighbour(coords, &direction);
+                if indices[&neighbour] > indices[coords]
+                    && maze.borrow_cell(&neighbour).crossing == Crossing::None
+                {
+                    walls.push((*coords, direction));
+                }
+            }
+        }
+        walls.shuffle(rng);
+
+        for (coords, direction) in walls {
+            let neighbour = maze.get_neighbour(&coords, &direction);
+            if sets.union(indices[&coords], indices[&neighbour]) {
+                maze.carve_path(&coords, &direction);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WeaveGenerator {
+    ///Turns `coords` into a crossing if none of its four sides is carved yet, which also keeps
+    ///crossings apart, and neither passage would close a loop. Returns true if it did.
+    fn add_crossing(
+        maze: &mut Maze,
+        coords: &Coords,
+        indices: &HashMap<Coords, usize>,
+        sets: &mut DisjointSet,
+        rng: &mut dyn RngCore,
+    ) -> bool {
+        if maze.get_directions_possible(coords).len() != 4 {
+            return false;
+        }
+        let cell = maze.borrow_cell(coords);
+        if [&cell.top, &cell.right, &cell.bottom, &cell.left]
+            .iter()
+            .any(|link| **link != LinkType::Wall)
+        {
+            return false;
+        }
+        let neighbour_index =
+            |direction: Direction| indices[&maze.get_neighbour(coords, &direction)];
+        let (up, right) = (
+            neighbour_index(Direction::Up),
+            neighbour_index(Direction::Right),
+        );
+        let (down, left) = (
+            neighbour_index(Direction::Down),
+            neighbour_index(Direction::Left),
+        );
+        if sets.same_set(left, right) || sets.same_set(up, down) {
+            return false;
+        }
+
+        let crossing = if rng.gen_bool(0.5) {
+            Crossing::VerticalUnder
+        } else {
+            Crossing::HorizontalUnder
+        };
+        for direction in [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ] {
+            maze.carve_path(coords, &direction);
+        }
+        maze.borrow_cell_mut(coords).crossing = crossing;
+
+        let over = indices[coords];
+        if crossing == Crossing::VerticalUnder {
+            sets.union(left, over);
+            sets.union(over, right);
+            sets.union(up, down);
+        } else {
+            sets.union(up, over);
+            sets.union(over, down);
+            sets.union(left, right);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests_weave {
+    use super::WeaveGenerator;
+    use crate::grid::Grid;
+    use crate::maze::{Coords, Crossing, Direction};
+    use crate::maze_generator::tests_generator::generate_spanning_tree;
+
+    #[test]
+    pub fn test_crossings_are_never_adjacent() {
+        for seed in 0..5 {
+            let generator = Box::new(WeaveGenerator {
+                crossing_probability: 1.0,
+            });
+            //every tunnel is one edge of the spanning tree, the crossing cell is not on it
+            let maze = generate_spanning_tree(generator, 20, 15, seed);
+            let cells = maze.nodes();
+            let crossings: Vec<&Coords> = cells
+                .iter()
+                .filter(|coords| maze.borrow_cell(coords).crossing != Crossing::None)
+                .collect();
+            assert!(!crossings.is_empty());
+
+            for crossing in crossings {
+                assert_eq!(maze.links(crossing).len(), 2);
+                for direction in [
+                    Direction::Up,
+                    Direction::Right,
+                    Direction::Down,
+                    Direction::Left,
+                ] {
+                    let neighbour = maze.get_neighbour(crossing, &direction);
+                    assert_eq!(maze.borrow_cell(&neighbour).crossing, Crossing::None);
+                }
+            }
+        }
+    }
+}
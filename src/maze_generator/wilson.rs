@@ -1,120 +1,84 @@
-use crate::maze::{Coords, Direction, LinkType, Maze, MazeGenerator};
+use crate::grid::{Grid, GridGenerator};
+use crate::maze::{Maze, MazeGenerator};
 use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::collections::HashMap;
 use std::io;
 
 pub struct WilsonGenerator;
 
 impl MazeGenerator for WilsonGenerator {
-    fn generate(&self, maze: &mut Maze) -> Result<(), io::Error> {
-        let (width, height) = maze.get_dimensions();
-        let mut remaining = width * height - 1;
-        maze.borrow_cell_mut(&Coords { x: 0, y: 0 }).in_maze = true;
-        let mut curr_coords = Coords { x: 1, y: 0 };
-        while remaining > 0 {
-            while maze.borrow_cell(&curr_coords).in_maze {
-                if curr_coords.x < width - 1 {
-                    curr_coords.x += 1;
-                } else {
-                    curr_coords.x = 0;
-                    curr_coords.y += 1;
-                    if curr_coords.y > height - 1 {
-                        return Err(io::Error::new(io::ErrorKind::Other, "Exceeded maze range"));
-                    }
-                }
-            }
-            WilsonGenerator::random_walk(maze, &curr_coords)?;
-
-            let count = WilsonGenerator::follow_path(maze, &curr_coords)?;
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        self.generate_grid(maze, rng)
+    }
+}
 
-            remaining -= count;
-        }
-        Ok(())
+impl GridGenerator for WilsonGenerator {
+    fn generate_grid<G: Grid>(&self, grid: &mut G, rng: &mut dyn RngCore) -> Result<(), io::Error> {
+        let start = grid.start();
+        grid.set_in_maze(&start, true);
+        WilsonGenerator::connect_remaining(grid, rng)
     }
 }
 
 impl WilsonGenerator {
-    fn random_walk(maze: &mut Maze, start_coords: &Coords) -> Result<(), io::Error> {
-        let mut current_coords = start_coords.clone();
-
-        while !maze.borrow_cell(&current_coords).in_maze {
-            let possible_directions = maze.get_directions_possible(&current_coords);
-            let rand_dir = possible_directions.choose(&mut rand::thread_rng()).unwrap();
-
-            maze.borrow_cell_mut(&current_coords).direction = rand_dir.clone();
-
-            match rand_dir {
-                Direction::Down => {
-                    current_coords.y += 1;
-                }
-
-                Direction::Up => {
-                    current_coords.y -= 1;
-                }
-
-                Direction::Left => {
-                    current_coords.x -= 1;
-                }
-
-                Direction::Right => {
-                    current_coords.x += 1;
-                }
-
-                Direction::Blank => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        "No direction available",
-                    ));
-                }
+    ///Attaches every node that is not yet in maze with loop-erased random walks,
+    ///at least one node has to be in maze already
+    pub(crate) fn connect_remaining<G: Grid>(
+        grid: &mut G,
+        rng: &mut dyn RngCore,
+    ) -> Result<(), io::Error> {
+        let mut walk: HashMap<G::Node, G::Node> = HashMap::new();
+        for node in grid.nodes() {
+            if grid.is_in_maze(&node) {
+                continue;
             }
+            WilsonGenerator::random_walk(grid, &node, &mut walk, rng)?;
+            WilsonGenerator::follow_path(grid, &node, &walk)?;
+            walk.clear();
         }
         Ok(())
     }
 
-    fn follow_path(maze: &mut Maze, coords: &Coords) -> Result<usize, io::Error> {
-        let mut current_coords = coords.clone();
-        let mut count: usize = 0;
-
-        while !maze.borrow_cell(&current_coords).in_maze {
-            match maze.borrow_cell(&current_coords).direction {
-                Direction::Up => {
-                    let cell = maze.borrow_cell_mut(&current_coords);
-                    cell.in_maze = true;
-                    cell.top = LinkType::Path;
-                    current_coords.y -= 1;
-                    maze.borrow_cell_mut(&current_coords).bottom = LinkType::Path;
-                }
-
-                Direction::Down => {
-                    let cell = maze.borrow_cell_mut(&current_coords);
-                    cell.in_maze = true;
-                    cell.bottom = LinkType::Path;
-                    current_coords.y += 1;
-                    maze.borrow_cell_mut(&current_coords).top = LinkType::Path;
-                }
-
-                Direction::Left => {
-                    let cell = maze.borrow_cell_mut(&current_coords);
-                    cell.in_maze = true;
-                    cell.left = LinkType::Path;
-                    current_coords.x -= 1;
-                    maze.borrow_cell_mut(&current_coords).right = LinkType::Path;
-                }
+    ///Walks randomly from `start` until it hits the maze, remembering the last exit of every node.
+    ///Overwriting the exit when a node is revisited erases the loops of the walk.
+    pub(crate) fn random_walk<G: Grid>(
+        grid: &G,
+        start: &G::Node,
+        walk: &mut HashMap<G::Node, G::Node>,
+        rng: &mut dyn RngCore,
+    ) -> Result<(), io::Error> {
+        let mut current = *start;
+
+        while !grid.is_in_maze(&current) {
+            let neighbours = grid.neighbours(&current);
+            let next = *neighbours.choose(rng).ok_or(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "No direction available",
+            ))?;
+            walk.insert(current, next);
+            current = next;
+        }
+        Ok(())
+    }
 
-                Direction::Right => {
-                    let cell = maze.borrow_cell_mut(&current_coords);
-                    cell.in_maze = true;
-                    cell.right = LinkType::Path;
-                    current_coords.x += 1;
-                    maze.borrow_cell_mut(&current_coords).left = LinkType::Path;
-                }
+    ///Carves the loop-erased walk from `start` into the maze, returns the number of nodes added
+    pub(crate) fn follow_path<G: Grid>(
+        grid: &mut G,
+        start: &G::Node,
+        walk: &HashMap<G::Node, G::Node>,
+    ) -> Result<usize, io::Error> {
+        let mut current = *start;
+        let mut count: usize = 0;
 
-                Direction::Blank => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        "No direction available",
-                    ));
-                }
-            }
+        while !grid.is_in_maze(&current) {
+            let next = *walk.get(&current).ok_or(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "No direction available",
+            ))?;
+            grid.set_in_maze(&current, true);
+            grid.link(&current, &next);
+            current = next;
             count += 1;
         }
 
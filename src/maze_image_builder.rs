@@ -1,7 +1,23 @@
+use crate::grid::Grid;
+use crate::hex_maze::{HexDirection, HexMaze};
 use crate::image_drawer::GenericImageExt;
 use crate::maze::{self, Coords, Maze};
+use crate::maze_3d::{Coords3D, Direction3D, Maze3D};
+use crate::polar_maze::PolarMaze;
 use crate::solver;
+use crate::triangle_maze::{TriangleDirection, TriangleMaze};
 use image::{Rgb, RgbImage};
+use std::f32::consts::PI;
+
+///Colours of the keys and doors, cycled through when there are more locks
+const LOCK_COLORS: [Rgb<u8>; 6] = [
+    Rgb([0x00, 0x80, 0xff]),
+    Rgb([0x00, 0xb0, 0x40]),
+    Rgb([0xff, 0x90, 0x00]),
+    Rgb([0xa0, 0x30, 0xe0]),
+    Rgb([0x00, 0xc0, 0xc0]),
+    Rgb([0xe0, 0xc0, 0x00]),
+];
 
 pub struct ConfigArray {
     pub cell_width: u32,
@@ -13,16 +29,19 @@ pub struct MazeImageBuilder<'a> {
     image: RgbImage,
     maze: &'a Maze,
     solve: bool,
+    ///Outer walls left open at the entry and the exits
+    openings: Vec<(Coords, maze::Direction)>,
 }
 
 impl<'a> MazeImageBuilder<'a> {
     pub fn new(config: ConfigArray, maze: &'a Maze) -> MazeImageBuilder<'a> {
         let (width, height) = MazeImageBuilder::get_image_array_size(maze, &config);
         let mut ret = MazeImageBuilder {
-            config: config,
-            maze: maze,
-            image: RgbImage::from_pixel(width, height, Rgb { 0: [0xff; 3] }),
+            config,
+            maze,
+            image: RgbImage::from_pixel(width, height, Rgb([0xff; 3])),
             solve: false,
+            openings: MazeImageBuilder::get_openings(maze),
         };
         ret.draw_maze();
         ret
@@ -32,7 +51,28 @@ impl<'a> MazeImageBuilder<'a> {
         let width_array = maze.get_width() as u32 * (config.cell_width - 1) + 1;
         let height_array = maze.get_height() as u32 * (config.cell_height - 1) + 1;
 
-        (width_array as u32, height_array as u32)
+        (width_array, height_array)
+    }
+
+    ///The entry opens on its left or top side, exits on their right or bottom side.
+    ///Cells away from the outer wall stay closed.
+    fn get_openings(maze: &Maze) -> Vec<(Coords, maze::Direction)> {
+        let mut openings = Vec::new();
+        let start = maze.get_start_point();
+        if let Some(side) = maze.get_outer_sides(start).into_iter().next() {
+            openings.push((*start, side));
+        }
+        for exit in maze.get_exits() {
+            if let Some(side) = maze.get_outer_sides(&exit).into_iter().next_back() {
+                openings.push((exit, side));
+            }
+        }
+        openings
+    }
+
+    fn is_wall(&self, coords: &Coords, direction: maze::Direction) -> bool {
+        self.maze.borrow_cell(coords).get_link(&direction) == &maze::LinkType::Wall
+            && !self.openings.contains(&(*coords, direction))
     }
 
     pub fn draw_cell(&mut self, x: u32, y: u32) {
@@ -70,66 +110,161 @@ impl<'a> MazeImageBuilder<'a> {
             y: y as usize,
         };
         let cell = self.maze.borrow_cell(&coords);
+        if cell.crossing != maze::Crossing::None {
+            self.draw_crossing(&coords, thickness_horizontal.max(thickness_vertical));
+            return;
+        }
+
         //draw top line
-        if cell.top == maze::LinkType::Wall {
+        if self.is_wall(&coords, maze::Direction::Up) {
             self.image.draw_line_with_thickness(
                 &top_left_corner,
                 &top_right_corner,
                 thickness_vertical,
-                Rgb { 0: [0x00; 3] },
+                Rgb([0x00; 3]),
             );
         }
 
         //draw left line
-        if cell.left == maze::LinkType::Wall {
+        if self.is_wall(&coords, maze::Direction::Left) {
             self.image.draw_line_with_thickness(
                 &top_left_corner,
                 &bottom_left_corner,
                 thickness_horizontal,
-                Rgb { 0: [0x00; 3] },
+                Rgb([0x00; 3]),
             );
         }
 
         //draw right line
-        if cell.right == maze::LinkType::Wall {
+        if self.is_wall(&coords, maze::Direction::Right) {
             self.image.draw_line_with_thickness(
                 &top_right_corner,
                 &bottom_right_corner,
                 thickness_horizontal,
-                Rgb { 0: [0x00; 3] },
+                Rgb([0x00; 3]),
             );
         }
 
         //draw bottom line
-        if cell.bottom == maze::LinkType::Wall {
+        if self.is_wall(&coords, maze::Direction::Down) {
             self.image.draw_line_with_thickness(
                 &bottom_left_corner,
                 &bottom_right_corner,
                 thickness_vertical,
-                Rgb { 0: [0x00; 3] },
+                Rgb([0x00; 3]),
             );
         }
     }
 
+    ///Walls of the passage going over the cell drawn straight through, walls of the tunnel
+    ///only up to them, so the tunnel shows as a gap under the upper passage
+    fn draw_crossing(&mut self, coords: &Coords, thickness: u32) {
+        let width_cell = self.config.cell_width;
+        let height_cell = self.config.cell_height;
+        let (base_x, base_y) = (
+            (width_cell - 1) * coords.x as u32,
+            (height_cell - 1) * coords.y as u32,
+        );
+        let (end_x, end_y) = (base_x + width_cell - 1, base_y + height_cell - 1);
+        let (inset_x, inset_y) = (width_cell / 4, height_cell / 4);
+        let point = |x: u32, y: u32| Coords {
+            x: x as usize,
+            y: y as usize,
+        };
+
+        let mut lines = Vec::new();
+        match self.maze.borrow_cell(coords).crossing {
+            maze::Crossing::VerticalUnder => {
+                for y in [base_y + inset_y, end_y - inset_y] {
+                    lines.push((point(base_x, y), point(end_x, y)));
+                }
+                for x in [base_x + inset_x, end_x - inset_x] {
+                    lines.push((point(x, base_y), point(x, base_y + inset_y)));
+                    lines.push((point(x, end_y - inset_y), point(x, end_y)));
+                }
+            }
+            maze::Crossing::HorizontalUnder => {
+                for x in [base_x + inset_x, end_x - inset_x] {
+                    lines.push((point(x, base_y), point(x, end_y)));
+                }
+                for y in [base_y + inset_y, end_y - inset_y] {
+                    lines.push((point(base_x, y), point(base_x + inset_x, y)));
+                    lines.push((point(end_x - inset_x, y), point(end_x, y)));
+                }
+            }
+            maze::Crossing::None => {}
+        }
+        for (start, end) in lines {
+            self.image
+                .draw_line_with_thickness(&start, &end, thickness, Rgb([0x00; 3]));
+        }
+    }
+
     pub fn build_image(mut self) -> RgbImage {
         self.draw_maze();
         if self.solve {
             self.draw_solution();
         }
+        self.draw_locks();
         self.image
     }
 
+    ///Each key as a square in its cell and its door as a bar across the passage, in the same colour
+    fn draw_locks(&mut self) {
+        let width_cell = self.config.cell_width;
+        let height_cell = self.config.cell_height;
+        let key_size = (width_cell.min(height_cell) / 2).max(1);
+        let thickness = (((width_cell.min(height_cell) as f32) * 0.25).round() as u32).max(1);
+
+        for (index, lock) in self.maze.get_locks().iter().enumerate() {
+            let color = LOCK_COLORS[index % LOCK_COLORS.len()];
+            let key_center = self.get_center(&lock.key);
+            self.image
+                .draw_full_square_with_center(&key_center, key_size, color);
+
+            let (from, to) = lock.door;
+            let Some((direction, _)) = self
+                .maze
+                .get_moves_with_directions(&from)
+                .into_iter()
+                .find(|(_, neighbour)| *neighbour == to)
+            else {
+                continue;
+            };
+            let base_x = ((width_cell - 1) * from.x as u32) as usize;
+            let base_y = ((height_cell - 1) * from.y as u32) as usize;
+            let end_x = base_x + width_cell as usize - 1;
+            let end_y = base_y + height_cell as usize - 1;
+            let point = |x, y| Coords { x, y };
+            let (start, end) = match direction {
+                maze::Direction::Left => (point(base_x, base_y), point(base_x, end_y)),
+                maze::Direction::Up => (point(base_x, base_y), point(end_x, base_y)),
+                maze::Direction::Right => (point(end_x, base_y), point(end_x, end_y)),
+                _ => (point(base_x, end_y), point(end_x, end_y)),
+            };
+            self.image
+                .draw_line_with_thickness(&start, &end, thickness, color);
+        }
+    }
+
     fn draw_maze(&mut self) {
         let (width, height) = self.maze.get_dimensions();
         for y in 0..height {
             for x in 0..width {
+                if !self.maze.is_active(&Coords { x, y }) {
+                    continue;
+                }
                 self.draw_cell(x as u32, y as u32);
             }
         }
     }
 
     fn draw_solution(&mut self) {
-        let path = solver::solve_maze(&self.maze).unwrap();
+        let path = if self.maze.get_locks().is_empty() {
+            solver::solve_maze(self.maze).unwrap()
+        } else {
+            solver::solve_with_keys(self.maze).unwrap()
+        };
         let width_cell = self.config.cell_width;
         let height_cell = self.config.cell_height;
         let thickness_horizontal = (((width_cell as f32) * 0.2).round() as u32).max(1);
@@ -137,25 +272,638 @@ impl<'a> MazeImageBuilder<'a> {
         let thickness = thickness_horizontal.max(thickness_vertical);
         let color = Rgb([0xff, 0x00, 0x00]);
 
-        for i in 0..path.data.len() - 1 {
-            let coords_first = path.data.get(i).unwrap();
-            let coords_second = path.data.get(i + 1).unwrap();
-            let base_x0 = (width_cell - 1) * coords_first.x as u32 + (width_cell - 1) / 2;
-            let base_y0 = (height_cell - 1) * coords_first.y as u32 + (height_cell - 1) / 2;
+        for pair in path.data.windows(2) {
+            //a move through a tunnel skips the crossing cells, walk it one cell at a time
+            let direction = self
+                .maze
+                .get_moves_with_directions(&pair[0])
+                .into_iter()
+                .find(|(_, neighbour)| *neighbour == pair[1])
+                .map(|(direction, _)| direction)
+                .unwrap();
+            let mut current = pair[0];
+            while current != pair[1] {
+                let next = self.maze.get_neighbour(&current, &direction);
+                self.draw_solution_step(&current, &next, &direction, thickness, color);
+                current = next;
+            }
+        }
+    }
+
+    fn draw_solution_step(
+        &mut self,
+        from: &Coords,
+        to: &Coords,
+        direction: &maze::Direction,
+        thickness: u32,
+        color: Rgb<u8>,
+    ) {
+        let begin = self.get_center(from);
+        let end = self.get_center(to);
+        if begin.x.abs_diff(end.x) <= self.config.cell_width as usize
+            && begin.y.abs_diff(end.y) <= self.config.cell_height as usize
+        {
+            self.image
+                .draw_line_with_thickness(&begin, &end, thickness, color);
+            return;
+        }
+
+        //the step wraps around the grid, leave through one border and come back through the other
+        let (width, height) = (self.image.width() as usize, self.image.height() as usize);
+        let point = |x, y| Coords { x, y };
+        let (exit, entry) = match direction {
+            maze::Direction::Left => (point(0, begin.y), point(width - 1, end.y)),
+            maze::Direction::Right => (point(width - 1, begin.y), point(0, end.y)),
+            maze::Direction::Up => (point(begin.x, 0), point(end.x, height - 1)),
+            _ => (point(begin.x, height - 1), point(end.x, 0)),
+        };
+        self.image
+            .draw_line_with_thickness(&begin, &exit, thickness, color);
+        self.image
+            .draw_line_with_thickness(&entry, &end, thickness, color);
+    }
+
+    fn get_center(&self, coords: &Coords) -> Coords {
+        let width_cell = self.config.cell_width;
+        let height_cell = self.config.cell_height;
+        Coords {
+            x: ((width_cell - 1) * coords.x as u32 + (width_cell - 1) / 2) as usize,
+            y: ((height_cell - 1) * coords.y as u32 + (height_cell - 1) / 2) as usize,
+        }
+    }
+
+    pub fn solve(&mut self, flag: bool) {
+        self.solve = flag;
+    }
+}
+
+pub struct HexMazeImageBuilder<'a> {
+    config: ConfigArray,
+    image: RgbImage,
+    maze: &'a HexMaze,
+    solve: bool,
+}
+
+impl<'a> HexMazeImageBuilder<'a> {
+    pub fn new(config: ConfigArray, maze: &'a HexMaze) -> HexMazeImageBuilder<'a> {
+        let (width, height) = HexMazeImageBuilder::get_image_array_size(maze, &config);
+        HexMazeImageBuilder {
+            config,
+            maze,
+            image: RgbImage::from_pixel(width, height, Rgb([0xff; 3])),
+            solve: false,
+        }
+    }
+
+    ///Circumradius of the hexagons, `cell_width` is the distance between two opposite corners
+    fn radius(config: &ConfigArray) -> f32 {
+        (config.cell_width as f32 / 2.0).max(2.0)
+    }
 
-            let base_x1 = (width_cell - 1) * coords_second.x as u32 + (width_cell - 1) / 2;
-            let base_y1 = (height_cell - 1) * coords_second.y as u32 + (height_cell - 1) / 2;
+    fn thickness(&self) -> u32 {
+        ((HexMazeImageBuilder::radius(&self.config) * 0.2).round() as u32).max(1)
+    }
+
+    pub fn get_image_array_size(maze: &HexMaze, config: &ConfigArray) -> (u32, u32) {
+        let radius = HexMazeImageBuilder::radius(config);
+        let hex_height = 3f32.sqrt() * radius;
+        let width_array = 1.5 * radius * (maze.get_width() as f32 - 1.0) + 2.0 * radius;
+        let height_array = hex_height * (maze.get_height() as f32 + 0.5);
+
+        (
+            width_array.ceil() as u32 + 1,
+            height_array.ceil() as u32 + 1,
+        )
+    }
 
+    fn get_center(&self, coords: &Coords) -> (f32, f32) {
+        let radius = HexMazeImageBuilder::radius(&self.config);
+        let hex_height = 3f32.sqrt() * radius;
+        let shift = if coords.x % 2 == 1 { 0.5 } else { 0.0 };
+        (
+            radius + 1.5 * radius * coords.x as f32,
+            hex_height * (coords.y as f32 + 0.5 + shift),
+        )
+    }
+
+    ///Corner `index` of a hexagon, counted clockwise from the east corner
+    fn get_corner(&self, coords: &Coords, index: usize) -> Coords {
+        let radius = HexMazeImageBuilder::radius(&self.config);
+        let (center_x, center_y) = self.get_center(coords);
+        let angle = (60.0 * index as f32).to_radians();
+        Coords {
+            x: (center_x + radius * angle.cos()).round() as usize,
+            y: (center_y + radius * angle.sin()).round() as usize,
+        }
+    }
+
+    pub fn draw_cell(&mut self, coords: &Coords) {
+        let thickness = self.thickness();
+        let cell = self.maze.borrow_cell(coords);
+        for direction in HexDirection::ALL {
+            if cell.links[direction.index()] != maze::LinkType::Wall {
+                continue;
+            }
+            //the side facing direction i lies between corners i + 4 and i + 5
+            let start = self.get_corner(coords, (direction.index() + 4) % 6);
+            let end = self.get_corner(coords, (direction.index() + 5) % 6);
+            self.image
+                .draw_line_with_thickness(&start, &end, thickness, Rgb([0x00; 3]));
+        }
+    }
+
+    pub fn build_image(mut self) -> RgbImage {
+        self.draw_maze();
+        if self.solve {
+            self.draw_solution();
+        }
+        self.image
+    }
+
+    fn draw_maze(&mut self) {
+        for coords in self.maze.nodes() {
+            self.draw_cell(&coords);
+        }
+    }
+
+    fn draw_solution(&mut self) {
+        let maze = self.maze;
+        let path = solver::solve_grid(maze, &maze.start(), &maze.end()).unwrap();
+        let thickness = self.thickness().max(2);
+        let color = Rgb([0xff, 0x00, 0x00]);
+
+        for pair in path.windows(2) {
+            let (x0, y0) = self.get_center(&pair[0]);
+            let (x1, y1) = self.get_center(&pair[1]);
             let begin = Coords {
-                x: base_x0 as usize,
-                y: base_y0 as usize,
+                x: x0.round() as usize,
+                y: y0.round() as usize,
             };
-
             let end = Coords {
-                x: base_x1 as usize,
-                y: base_y1 as usize,
+                x: x1.round() as usize,
+                y: y1.round() as usize,
+            };
+            self.image
+                .draw_line_with_thickness(&begin, &end, thickness, color);
+        }
+    }
+
+    pub fn solve(&mut self, flag: bool) {
+        self.solve = flag;
+    }
+}
+
+pub struct PolarMazeImageBuilder<'a> {
+    config: ConfigArray,
+    image: RgbImage,
+    maze: &'a PolarMaze,
+    solve: bool,
+}
+
+impl<'a> PolarMazeImageBuilder<'a> {
+    pub fn new(config: ConfigArray, maze: &'a PolarMaze) -> PolarMazeImageBuilder<'a> {
+        let (width, height) = PolarMazeImageBuilder::get_image_array_size(maze, &config);
+        PolarMazeImageBuilder {
+            config,
+            maze,
+            image: RgbImage::from_pixel(width, height, Rgb([0xff; 3])),
+            solve: false,
+        }
+    }
+
+    ///Every ring is `cell_width` pixels high
+    pub fn get_image_array_size(maze: &PolarMaze, config: &ConfigArray) -> (u32, u32) {
+        let size = 2 * maze.get_ring_count() as u32 * config.cell_width + 2 * config.cell_width / 5;
+        (size + 1, size + 1)
+    }
+
+    fn thickness(&self) -> u32 {
+        (((self.config.cell_width as f32) * 0.1).round() as u32).max(1)
+    }
+
+    fn get_center(&self) -> Coords {
+        Coords {
+            x: self.image.width() as usize / 2,
+            y: self.image.height() as usize / 2,
+        }
+    }
+
+    ///Angle of the counter-clockwise side of the cell and angle covered by one cell of its ring
+    fn get_angles(&self, coords: &Coords) -> (f32, f32) {
+        let step = 2.0 * PI / self.maze.get_cell_count(coords.y) as f32;
+        (step * coords.x as f32, step)
+    }
+
+    fn get_point(&self, radius: f32, angle: f32) -> Coords {
+        let center = self.get_center();
+        Coords {
+            x: (center.x as f32 + radius * angle.cos()).round() as usize,
+            y: (center.y as f32 + radius * angle.sin()).round() as usize,
+        }
+    }
+
+    pub fn draw_cell(&mut self, coords: &Coords) {
+        if coords.y == 0 {
+            return;
+        }
+        let thickness = self.thickness();
+        let color = Rgb([0x00; 3]);
+        let ring_width = self.config.cell_width as f32;
+        let inner_radius = ring_width * coords.y as f32;
+        let outer_radius = inner_radius + ring_width;
+        let (angle, step) = self.get_angles(coords);
+        let center = self.get_center();
+        let cell = self.maze.borrow_cell(coords);
+
+        if cell.inward == maze::LinkType::Wall {
+            self.image.draw_arc_with_thickness(
+                &center,
+                inner_radius,
+                angle,
+                angle + step,
+                thickness,
+                color,
+            );
+        }
+
+        if cell.counter_clockwise == maze::LinkType::Wall {
+            let start = self.get_point(inner_radius, angle);
+            let end = self.get_point(outer_radius, angle);
+            self.image
+                .draw_line_with_thickness(&start, &end, thickness, color);
+        }
+
+        //outer border, left open at the entrance
+        let is_outer_ring = coords.y == self.maze.get_ring_count() - 1;
+        if is_outer_ring && coords != self.maze.get_start_point() {
+            self.image.draw_arc_with_thickness(
+                &center,
+                outer_radius,
+                angle,
+                angle + step,
+                thickness,
+                color,
+            );
+        }
+    }
+
+    pub fn build_image(mut self) -> RgbImage {
+        self.draw_maze();
+        if self.solve {
+            self.draw_solution();
+        }
+        self.image
+    }
+
+    fn draw_maze(&mut self) {
+        for coords in self.maze.nodes() {
+            self.draw_cell(&coords);
+        }
+    }
+
+    ///Middle of the cell, as radius and angle
+    fn get_cell_middle(&self, coords: &Coords) -> (f32, f32) {
+        if coords.y == 0 {
+            return (0.0, 0.0);
+        }
+        let (angle, step) = self.get_angles(coords);
+        (
+            self.config.cell_width as f32 * (coords.y as f32 + 0.5),
+            angle + step / 2.0,
+        )
+    }
+
+    fn draw_solution(&mut self) {
+        let maze = self.maze;
+        let path = solver::solve_grid(maze, &maze.start(), &maze.end()).unwrap();
+        let thickness = (((self.config.cell_width as f32) * 0.2).round() as u32).max(1);
+        let color = Rgb([0xff, 0x00, 0x00]);
+        let center = self.get_center();
+
+        for pair in path.windows(2) {
+            let (radius, start_angle) = self.get_cell_middle(&pair[0]);
+            let (_, mut end_angle) = self.get_cell_middle(&pair[1]);
+            if pair[0].y == pair[1].y {
+                //moves inside a ring follow the ring, taking the short way across angle 0
+                if end_angle - start_angle > PI {
+                    end_angle -= 2.0 * PI;
+                } else if start_angle - end_angle > PI {
+                    end_angle += 2.0 * PI;
+                }
+                self.image.draw_arc_with_thickness(
+                    &center,
+                    radius,
+                    start_angle,
+                    end_angle,
+                    thickness,
+                    color,
+                );
+            } else {
+                let (end_radius, _) = self.get_cell_middle(&pair[1]);
+                let begin = self.get_point(radius, start_angle);
+                let end = self.get_point(end_radius, end_angle);
+                self.image
+                    .draw_line_with_thickness(&begin, &end, thickness, color);
+            }
+        }
+    }
+
+    pub fn solve(&mut self, flag: bool) {
+        self.solve = flag;
+    }
+}
+
+pub struct TriangleMazeImageBuilder<'a> {
+    config: ConfigArray,
+    image: RgbImage,
+    maze: &'a TriangleMaze,
+    solve: bool,
+}
+
+impl<'a> TriangleMazeImageBuilder<'a> {
+    pub fn new(config: ConfigArray, maze: &'a TriangleMaze) -> TriangleMazeImageBuilder<'a> {
+        let (width, height) = TriangleMazeImageBuilder::get_image_array_size(maze, &config);
+        TriangleMazeImageBuilder {
+            config,
+            maze,
+            image: RgbImage::from_pixel(width, height, Rgb([0xff; 3])),
+            solve: false,
+        }
+    }
+
+    ///Length of a triangle side, taken from `cell_width`
+    fn side(config: &ConfigArray) -> f32 {
+        (config.cell_width as f32).max(4.0)
+    }
+
+    fn triangle_height(config: &ConfigArray) -> f32 {
+        TriangleMazeImageBuilder::side(config) * 3f32.sqrt() / 2.0
+    }
+
+    fn thickness(&self) -> u32 {
+        ((TriangleMazeImageBuilder::side(&self.config) * 0.1).round() as u32).max(1)
+    }
+
+    pub fn get_image_array_size(maze: &TriangleMaze, config: &ConfigArray) -> (u32, u32) {
+        let side = TriangleMazeImageBuilder::side(config);
+        let width_array = side * (maze.get_width() as f32 + 1.0) / 2.0;
+        let height_array =
+            TriangleMazeImageBuilder::triangle_height(config) * maze.get_height() as f32;
+
+        (
+            width_array.ceil() as u32 + 1,
+            height_array.ceil() as u32 + 1,
+        )
+    }
+
+    ///Corners of a triangle: apex, then the two ends of the horizontal side from left to right
+    fn get_corners(&self, coords: &Coords) -> [(f32, f32); 3] {
+        let side = TriangleMazeImageBuilder::side(&self.config);
+        let height = TriangleMazeImageBuilder::triangle_height(&self.config);
+        let left = side * coords.x as f32 / 2.0;
+        let top = height * coords.y as f32;
+        if TriangleMaze::is_upward(coords) {
+            [
+                (left + side / 2.0, top),
+                (left, top + height),
+                (left + side, top + height),
+            ]
+        } else {
+            [
+                (left + side / 2.0, top + height),
+                (left, top),
+                (left + side, top),
+            ]
+        }
+    }
+
+    fn get_center(&self, coords: &Coords) -> (f32, f32) {
+        let corners = self.get_corners(coords);
+        (
+            corners.iter().map(|corner| corner.0).sum::<f32>() / 3.0,
+            corners.iter().map(|corner| corner.1).sum::<f32>() / 3.0,
+        )
+    }
+
+    fn to_pixel((x, y): (f32, f32)) -> Coords {
+        Coords {
+            x: x.round() as usize,
+            y: y.round() as usize,
+        }
+    }
+
+    pub fn draw_cell(&mut self, coords: &Coords) {
+        let thickness = self.thickness();
+        let cell = self.maze.borrow_cell(coords);
+        let [apex, horizontal_left, horizontal_right] = self.get_corners(coords);
+        for direction in TriangleDirection::ALL {
+            if cell.get_link(&direction) != &maze::LinkType::Wall {
+                continue;
+            }
+            let (start, end) = match direction {
+                TriangleDirection::Left => (apex, horizontal_left),
+                TriangleDirection::Right => (apex, horizontal_right),
+                TriangleDirection::Vertical => (horizontal_left, horizontal_right),
             };
+            self.image.draw_line_with_thickness(
+                &TriangleMazeImageBuilder::to_pixel(start),
+                &TriangleMazeImageBuilder::to_pixel(end),
+                thickness,
+                Rgb([0x00; 3]),
+            );
+        }
+    }
+
+    pub fn build_image(mut self) -> RgbImage {
+        self.draw_maze();
+        if self.solve {
+            self.draw_solution();
+        }
+        self.image
+    }
+
+    fn draw_maze(&mut self) {
+        for coords in self.maze.nodes() {
+            self.draw_cell(&coords);
+        }
+    }
+
+    fn draw_solution(&mut self) {
+        let maze = self.maze;
+        let path = solver::solve_grid(maze, &maze.start(), &maze.end()).unwrap();
+        let thickness = self.thickness().max(2);
+        let color = Rgb([0xff, 0x00, 0x00]);
 
+        for pair in path.windows(2) {
+            let begin = TriangleMazeImageBuilder::to_pixel(self.get_center(&pair[0]));
+            let end = TriangleMazeImageBuilder::to_pixel(self.get_center(&pair[1]));
+            self.image
+                .draw_line_with_thickness(&begin, &end, thickness, color);
+        }
+    }
+
+    pub fn solve(&mut self, flag: bool) {
+        self.solve = flag;
+    }
+}
+
+pub struct Maze3DImageBuilder<'a> {
+    config: ConfigArray,
+    image: RgbImage,
+    maze: &'a Maze3D,
+    solve: bool,
+}
+
+impl<'a> Maze3DImageBuilder<'a> {
+    pub fn new(config: ConfigArray, maze: &'a Maze3D) -> Maze3DImageBuilder<'a> {
+        let (width, height) = Maze3DImageBuilder::get_image_array_size(maze, &config);
+        Maze3DImageBuilder {
+            config,
+            maze,
+            image: RgbImage::from_pixel(width, height, Rgb([0xff; 3])),
+            solve: false,
+        }
+    }
+
+    ///Floors are drawn from left to right, one empty cell apart
+    pub fn get_image_array_size(maze: &Maze3D, config: &ConfigArray) -> (u32, u32) {
+        let floor_width = maze.get_width() as u32 * (config.cell_width - 1) + 1;
+        let depth = maze.get_depth() as u32;
+        let width_array = depth * floor_width + (depth - 1) * config.cell_width;
+        let height_array = maze.get_height() as u32 * (config.cell_height - 1) + 1;
+
+        (width_array, height_array)
+    }
+
+    ///Top left pixel of the cell
+    fn get_origin(&self, coords: &Coords3D) -> (u32, u32) {
+        let floor_width = self.maze.get_width() as u32 * (self.config.cell_width - 1) + 1;
+        let floor_x = coords.z as u32 * (floor_width + self.config.cell_width);
+        (
+            floor_x + (self.config.cell_width - 1) * coords.x as u32,
+            (self.config.cell_height - 1) * coords.y as u32,
+        )
+    }
+
+    fn get_center(&self, coords: &Coords3D) -> Coords {
+        let (base_x, base_y) = self.get_origin(coords);
+        Coords {
+            x: (base_x + (self.config.cell_width - 1) / 2) as usize,
+            y: (base_y + (self.config.cell_height - 1) / 2) as usize,
+        }
+    }
+
+    pub fn draw_cell(&mut self, coords: &Coords3D) {
+        let width_cell = self.config.cell_width;
+        let height_cell = self.config.cell_height;
+        let thickness_horizontal = (((width_cell as f32) * 0.1).round() as u32).max(1);
+        let thickness_vertical = (((height_cell as f32) * 0.1).round() as u32).max(1);
+        let (base_x, base_y) = self.get_origin(coords);
+        let (end_x, end_y) = (base_x + width_cell - 1, base_y + height_cell - 1);
+        let corner = |x: u32, y: u32| Coords {
+            x: x as usize,
+            y: y as usize,
+        };
+
+        let cell = self.maze.borrow_cell(coords);
+        for direction in Direction3D::ALL {
+            if cell.get_link(&direction) != &maze::LinkType::Wall {
+                continue;
+            }
+            let (start, end, thickness) = match direction {
+                Direction3D::West => (
+                    corner(base_x, base_y),
+                    corner(base_x, end_y),
+                    thickness_horizontal,
+                ),
+                Direction3D::North => (
+                    corner(base_x, base_y),
+                    corner(end_x, base_y),
+                    thickness_vertical,
+                ),
+                Direction3D::East => (
+                    corner(end_x, base_y),
+                    corner(end_x, end_y),
+                    thickness_horizontal,
+                ),
+                Direction3D::South => (
+                    corner(base_x, end_y),
+                    corner(end_x, end_y),
+                    thickness_vertical,
+                ),
+                Direction3D::Upstairs | Direction3D::Downstairs => continue,
+            };
+            self.image
+                .draw_line_with_thickness(&start, &end, thickness, Rgb([0x00; 3]));
+        }
+        self.draw_stairs(coords);
+    }
+
+    ///Chevron pointing up in the upper half of cells with stairs up,
+    ///pointing down in the lower half of cells with stairs down
+    fn draw_stairs(&mut self, coords: &Coords3D) {
+        let cell = self.maze.borrow_cell(coords);
+        let center = self.get_center(coords);
+        let half_width = (self.config.cell_width / 4) as usize;
+        let quarter_height = (self.config.cell_height / 4) as usize;
+        let thickness = (((self.config.cell_width as f32) * 0.08).round() as u32).max(1);
+        let color = Rgb([0x00, 0x00, 0xff]);
+
+        let mut chevrons = Vec::new();
+        if cell.get_link(&Direction3D::Upstairs) == &maze::LinkType::Path {
+            let tip = center.y - quarter_height;
+            chevrons.push((tip, center.y));
+        }
+        if cell.get_link(&Direction3D::Downstairs) == &maze::LinkType::Path {
+            let tip = center.y + quarter_height;
+            chevrons.push((tip, center.y));
+        }
+        for (tip_y, wings_y) in chevrons {
+            let tip = Coords {
+                x: center.x,
+                y: tip_y,
+            };
+            for wing_x in [center.x - half_width, center.x + half_width] {
+                let wing = Coords {
+                    x: wing_x,
+                    y: wings_y,
+                };
+                self.image
+                    .draw_line_with_thickness(&wing, &tip, thickness, color);
+            }
+        }
+    }
+
+    pub fn build_image(mut self) -> RgbImage {
+        self.draw_maze();
+        if self.solve {
+            self.draw_solution();
+        }
+        self.image
+    }
+
+    fn draw_maze(&mut self) {
+        for coords in self.maze.nodes() {
+            self.draw_cell(&coords);
+        }
+    }
+
+    ///Solution segments inside a floor, taking the stairs shows as a jump between floors
+    fn draw_solution(&mut self) {
+        let path = solver::solve_maze(self.maze).unwrap();
+        let width_cell = self.config.cell_width;
+        let height_cell = self.config.cell_height;
+        let thickness_horizontal = (((width_cell as f32) * 0.2).round() as u32).max(1);
+        let thickness_vertical = (((height_cell as f32) * 0.2).round() as u32).max(1);
+        let thickness = thickness_horizontal.max(thickness_vertical);
+        let color = Rgb([0xff, 0x00, 0x00]);
+
+        for pair in path.data.windows(2) {
+            if pair[0].z != pair[1].z {
+                continue;
+            }
+            let begin = self.get_center(&pair[0]);
+            let end = self.get_center(&pair[1]);
             self.image
                 .draw_line_with_thickness(&begin, &end, thickness, color);
         }
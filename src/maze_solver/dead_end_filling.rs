@@ -0,0 +1,113 @@
+use crate::maze::{Coords, Maze};
+use crate::maze_solver::{Solver, Step, Trace};
+use crate::solver::{Path, SolverError};
+use rand::RngCore;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+///Fills every dead end back to the junction it branches off from, until only the routes
+///between the start and the exits are left open. In a perfect maze that is the solution,
+///in a braided maze the shortest route through what is left is taken.
+pub struct DeadEndFillingSolver;
+
+impl Solver for DeadEndFillingSolver {
+    fn solve(&self, maze: &Maze, _rng: &mut dyn RngCore) -> Result<Trace, SolverError> {
+        let start = *maze.get_start_point();
+        let exits = maze.get_exits();
+        let kept = |coords: &Coords| *coords == start || exits.contains(coords);
+        let cells = maze.get_active_cells();
+        let mut open_moves: HashMap<Coords, usize> = cells
+            .iter()
+            .map(|coords| (*coords, maze.get_possible_moves(coords).len()))
+            .collect();
+
+        let mut filled: HashSet<Coords> = HashSet::new();
+        let mut steps = Vec::new();
+        let mut dead_ends: VecDeque<Coords> = cells
+            .iter()
+            .filter(|coords| open_moves[coords] <= 1 && !kept(coords))
+            .copied()
+            .collect();
+        while let Some(coords) = dead_ends.pop_front() {
+            if !filled.insert(coords) {
+                continue;
+            }
+            steps.push(Step::Fill(coords));
+            for neighbour in maze.get_possible_moves(&coords) {
+                if filled.contains(&neighbour) {
+                    continue;
+                }
+                let moves = open_moves.get_mut(&neighbour).unwrap();
+                *moves -= 1;
+                if *moves <= 1 && !kept(&neighbour) {
+                    dead_ends.push_back(neighbour);
+                }
+            }
+        }
+
+        let path = shortest_open_route(maze, &start, &exits, &filled)?;
+        steps.extend(path.data.iter().map(|coords| Step::Move(*coords)));
+        Ok(Trace { steps, path })
+    }
+}
+
+///Breadth-first search through the cells left open
+fn shortest_open_route(
+    maze: &Maze,
+    start: &Coords,
+    exits: &[Coords],
+    filled: &HashSet<Coords>,
+) -> Result<Path, SolverError> {
+    let mut parents: HashMap<Coords, Coords> = HashMap::new();
+    let mut queue = VecDeque::from([*start]);
+    while let Some(coords) = queue.pop_front() {
+        if exits.contains(&coords) {
+            let mut data = vec![coords];
+            while data.last() != Some(start) {
+                data.push(parents[data.last().unwrap()]);
+            }
+            data.reverse();
+            return Ok(Path { data });
+        }
+        for neighbour in maze.get_possible_moves(&coords) {
+            if neighbour == *start
+                || filled.contains(&neighbour)
+                || parents.contains_key(&neighbour)
+            {
+                continue;
+            }
+            parents.insert(neighbour, coords);
+            queue.push_back(neighbour);
+        }
+    }
+    Err(SolverError::EndNotReached)
+}
+
+#[cfg(test)]
+mod tests_dead_end_filling {
+    use super::DeadEndFillingSolver;
+    use crate::maze::MazeBuilder;
+    use crate::maze_generator::kruskal::KruskalGenerator;
+    use crate::maze_solver::{Solver, Step};
+    use crate::solver::solve_maze;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    pub fn test_only_the_solution_is_left_open() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let maze = MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_seed(11)
+            .generate(12, 12)
+            .unwrap();
+        let trace = DeadEndFillingSolver.solve(&maze, &mut rng).unwrap();
+        let shortest = solve_maze(&maze).unwrap().data;
+        assert_eq!(trace.path.data, shortest);
+
+        let filled = trace
+            .steps
+            .iter()
+            .filter(|step| matches!(step, Step::Fill(_)))
+            .count();
+        assert_eq!(filled + shortest.len(), 12 * 12);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::maze::{Coords, Maze};
+use crate::solver::{Path, SolverError};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub mod dead_end_filling;
+pub mod random_mouse;
+pub mod tremaux;
+pub mod wall_follower;
+
+///Strategy a person could follow to get out of a maze, recording everything it does on the way
+pub trait Solver {
+    ///Walks from the start to the nearest of the exits, locked doors are ignored
+    fn solve(&self, maze: &Maze, rng: &mut dyn RngCore) -> Result<Trace, SolverError>;
+}
+
+///One action of a solver, in the order it was taken
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Step {
+    ///Walked into the cell
+    Move(Coords),
+    ///Ruled the cell out without walking into it
+    Fill(Coords),
+}
+
+///Everything a solver did, with the route to the exit it ended up with
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+    pub path: Path,
+}
+
+impl Trace {
+    ///Trace of a walker, the route is the walk with every loop it made cut out
+    pub(crate) fn from_walk(walk: Vec<Coords>) -> Trace {
+        let path = Path {
+            data: erase_loops(&walk),
+        };
+        Trace {
+            steps: walk.into_iter().map(Step::Move).collect(),
+            path,
+        }
+    }
+}
+
+///Cuts every loop out of a walk, each cell is left in the same order as it was first reached
+pub(crate) fn erase_loops(walk: &[Coords]) -> Vec<Coords> {
+    let mut route: Vec<Coords> = Vec::new();
+    let mut positions: HashMap<Coords, usize> = HashMap::new();
+    for coords in walk {
+        if let Some(position) = positions.get(coords).copied() {
+            for erased in route.drain(position + 1..) {
+                positions.remove(&erased);
+            }
+            continue;
+        }
+        positions.insert(*coords, route.len());
+        route.push(*coords);
+    }
+    route
+}
+
+#[cfg(test)]
+mod tests_maze_solver {
+    use super::dead_end_filling::DeadEndFillingSolver;
+    use super::random_mouse::RandomMouseSolver;
+    use super::tremaux::TremauxSolver;
+    use super::wall_follower::{Hand, WallFollowerSolver};
+    use super::{Solver, Step};
+    use crate::maze::{Coords, Direction, MazeBuilder, Placement};
+    use crate::maze_generator::kruskal::KruskalGenerator;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn solvers() -> Vec<Box<dyn Solver>> {
+        vec![
+            Box::new(WallFollowerSolver { hand: Hand::Left }),
+            Box::new(WallFollowerSolver { hand: Hand::Right }),
+            Box::new(TremauxSolver),
+            Box::new(DeadEndFillingSolver),
+            Box::new(RandomMouseSolver {
+                max_steps: 1_000_000,
+            }),
+        ]
+    }
+
+    #[test]
+    pub fn test_every_move_goes_to_a_neighbour() {
+        for braid in [0.0, 0.5] {
+            let maze = MazeBuilder::from_generator(Box::new(KruskalGenerator))
+                .with_seed(4)
+                .with_braid(braid)
+                .generate(12, 9)
+                .unwrap();
+            for solver in solvers() {
+                let mut rng = ChaCha8Rng::seed_from_u64(1);
+                //wall following may give up on the braided maze, that is covered below
+                let Ok(trace) = solver.solve(&maze, &mut rng) else {
+                    continue;
+                };
+                let moves: Vec<Coords> = trace
+                    .steps
+                    .iter()
+                    .filter_map(|step| match step {
+                        Step::Move(coords) => Some(*coords),
+                        Step::Fill(_) => None,
+                    })
+                    .collect();
+                assert_eq!(moves.first(), Some(maze.get_start_point()));
+                assert!(maze.get_exits().contains(moves.last().unwrap()));
+                for pair in moves.windows(2) {
+                    assert!(maze.get_possible_moves(&pair[0]).contains(&pair[1]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_wall_follower_gives_up_on_a_loop_around_the_start() {
+        let start = Coords { x: 2, y: 2 };
+        let mut maze = MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_seed(4)
+            .with_start(Placement::At(start))
+            .generate(6, 6)
+            .unwrap();
+        //a ring through the four cells right and below the start, a right hand keeps circling it
+        maze.carve_path(&start, &Direction::Right);
+        maze.carve_path(&Coords { x: 3, y: 2 }, &Direction::Down);
+        maze.carve_path(&Coords { x: 3, y: 3 }, &Direction::Left);
+        maze.carve_path(&Coords { x: 2, y: 3 }, &Direction::Up);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        assert!(TremauxSolver.solve(&maze, &mut rng).is_ok());
+        assert!(WallFollowerSolver { hand: Hand::Right }
+            .solve(&maze, &mut rng)
+            .is_err());
+    }
+}
@@ -0,0 +1,68 @@
+use crate::maze::Maze;
+use crate::maze_generator::random_index;
+use crate::maze_solver::{Solver, Trace};
+use crate::solver::SolverError;
+use rand::RngCore;
+
+///Walks at random and only turns back in dead ends, slow but needs no memory at all
+pub struct RandomMouseSolver {
+    ///Moves after which the mouse gives up
+    pub max_steps: usize,
+}
+
+impl Solver for RandomMouseSolver {
+    fn solve(&self, maze: &Maze, rng: &mut dyn RngCore) -> Result<Trace, SolverError> {
+        let exits = maze.get_exits();
+        let mut coords = *maze.get_start_point();
+        let mut previous = None;
+        let mut walk = vec![coords];
+
+        while !exits.contains(&coords) {
+            if walk.len() > self.max_steps {
+                return Err(SolverError::EndNotReached);
+            }
+            let mut moves = maze.get_possible_moves(&coords);
+            if moves.len() > 1 {
+                moves.retain(|next| Some(*next) != previous);
+            }
+            if moves.is_empty() {
+                return Err(SolverError::EndNotReached);
+            }
+            previous = Some(coords);
+            coords = moves[random_index(rng, moves.len())];
+            walk.push(coords);
+        }
+        Ok(Trace::from_walk(walk))
+    }
+}
+
+#[cfg(test)]
+mod tests_random_mouse {
+    use super::RandomMouseSolver;
+    use crate::maze::MazeBuilder;
+    use crate::maze_generator::wilson::WilsonGenerator;
+    use crate::maze_solver::Solver;
+    use crate::solver::solve_maze;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    pub fn test_random_mouse_finds_the_exit() {
+        let maze = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(3)
+            .generate(8, 8)
+            .unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let trace = RandomMouseSolver {
+            max_steps: 1_000_000,
+        }
+        .solve(&maze, &mut rng)
+        .unwrap();
+        assert_eq!(trace.path.data, solve_maze(&maze).unwrap().data);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        assert!(RandomMouseSolver { max_steps: 3 }
+            .solve(&maze, &mut rng)
+            .is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::maze::{Coords, Maze};
+use crate::maze_generator::random_index;
+use crate::maze_solver::{Solver, Trace};
+use crate::solver::SolverError;
+use rand::RngCore;
+use std::collections::{HashMap, HashSet};
+
+///Trémaux's algorithm, marks every passage it walks through and never walks through one
+///a third time. Solves any maze, loops included.
+pub struct TremauxSolver;
+
+///Key of the passage between two cells, the same whichever end it is walked from
+fn passage(a: Coords, b: Coords) -> (Coords, Coords) {
+    if (a.y, a.x) <= (b.y, b.x) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+impl Solver for TremauxSolver {
+    fn solve(&self, maze: &Maze, rng: &mut dyn RngCore) -> Result<Trace, SolverError> {
+        let exits = maze.get_exits();
+        let mut marks: HashMap<(Coords, Coords), u8> = HashMap::new();
+        let mut coords = *maze.get_start_point();
+        let mut visited = HashSet::from([coords]);
+        let mut previous: Option<Coords> = None;
+        let mut walk = vec![coords];
+        let mut first_visit = true;
+
+        while !exits.contains(&coords) {
+            let moves = maze.get_possible_moves(&coords);
+            let mark_count =
+                |next: &Coords| marks.get(&passage(coords, *next)).copied().unwrap_or(0);
+            let unmarked: Vec<Coords> = moves
+                .iter()
+                .filter(|next| mark_count(next) == 0)
+                .copied()
+                .collect();
+            let back = previous.filter(|back| mark_count(back) < 2);
+
+            let next = match back {
+                //came back to a known junction through a new passage, that one leads nowhere new
+                Some(back) if !first_visit && mark_count(&back) == 1 => back,
+                _ if !unmarked.is_empty() => unmarked[random_index(rng, unmarked.len())],
+                Some(back) => back,
+                None => {
+                    let once: Vec<Coords> = moves
+                        .iter()
+                        .filter(|next| mark_count(next) == 1)
+                        .copied()
+                        .collect();
+                    if once.is_empty() {
+                        return Err(SolverError::EndNotReached);
+                    }
+                    once[random_index(rng, once.len())]
+                }
+            };
+
+            *marks.entry(passage(coords, next)).or_insert(0) += 1;
+            previous = Some(coords);
+            coords = next;
+            first_visit = visited.insert(coords);
+            walk.push(coords);
+        }
+        Ok(Trace::from_walk(walk))
+    }
+}
+
+#[cfg(test)]
+mod tests_tremaux {
+    use super::TremauxSolver;
+    use crate::maze::MazeBuilder;
+    use crate::maze_generator::wilson::WilsonGenerator;
+    use crate::maze_solver::{Solver, Step};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::HashMap;
+
+    #[test]
+    pub fn test_tremaux_solves_braided_mazes() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        for braid in [0.0, 0.6, 1.0] {
+            let maze = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+                .with_seed(7)
+                .with_braid(braid)
+                .generate(16, 12)
+                .unwrap();
+            let trace = TremauxSolver.solve(&maze, &mut rng).unwrap();
+            let route = &trace.path.data;
+            assert_eq!(route.first(), Some(maze.get_start_point()));
+            assert_eq!(route.last(), Some(maze.get_end_point()));
+            for pair in route.windows(2) {
+                assert!(maze.get_possible_moves(&pair[0]).contains(&pair[1]));
+            }
+
+            //no passage is walked through more than twice
+            let mut walked: HashMap<_, usize> = HashMap::new();
+            for pair in trace.steps.windows(2) {
+                let (Step::Move(a), Step::Move(b)) = (pair[0], pair[1]) else {
+                    panic!("Trémaux only moves");
+                };
+                *walked.entry(super::passage(a, b)).or_default() += 1;
+            }
+            assert!(walked.values().all(|count| *count <= 2));
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::maze::{Direction, Maze};
+use crate::maze_solver::{Solver, Trace};
+use crate::solver::SolverError;
+use rand::RngCore;
+use std::collections::HashSet;
+
+const CLOCKWISE: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+///Keeps one hand on the wall. Gets out of every perfect maze, but can circle forever around
+///a loop or a part of the maze the exits are not connected to, which is reported as unsolved.
+pub struct WallFollowerSolver {
+    pub hand: Hand,
+}
+
+impl WallFollowerSolver {
+    ///Directions to try when facing `facing`, the side of the hand first and back last
+    fn preferences(&self, facing: usize) -> [Direction; 4] {
+        let turns = match self.hand {
+            Hand::Right => [1, 0, 3, 2],
+            Hand::Left => [3, 0, 1, 2],
+        };
+        turns.map(|turn| CLOCKWISE[(facing + turn) % 4].clone())
+    }
+}
+
+impl Solver for WallFollowerSolver {
+    fn solve(&self, maze: &Maze, _rng: &mut dyn RngCore) -> Result<Trace, SolverError> {
+        let exits = maze.get_exits();
+        let mut coords = *maze.get_start_point();
+        //facing up, the first move has no wall to follow yet
+        let mut facing = 0;
+        let mut walk = vec![coords];
+        let mut seen = HashSet::new();
+
+        while !exits.contains(&coords) {
+            let moves = maze.get_moves_with_directions(&coords);
+            let (direction, next) = self
+                .preferences(facing)
+                .into_iter()
+                .find_map(|direction| moves.iter().find(|(d, _)| *d == direction).cloned())
+                .ok_or(SolverError::EndNotReached)?;
+            facing = CLOCKWISE.iter().position(|d| *d == direction).unwrap();
+            coords = next;
+            walk.push(coords);
+            //back to a cell facing the same way, the walk repeats from here on
+            if !seen.insert((coords, facing)) {
+                return Err(SolverError::EndNotReached);
+            }
+        }
+        Ok(Trace::from_walk(walk))
+    }
+}
+
+#[cfg(test)]
+mod tests_wall_follower {
+    use super::{Hand, WallFollowerSolver};
+    use crate::maze::MazeBuilder;
+    use crate::maze_generator::{kruskal::KruskalGenerator, weave::WeaveGenerator};
+    use crate::maze_solver::{Solver, Step};
+    use crate::solver::solve_maze;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    pub fn test_both_hands_solve_perfect_mazes() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let maze = MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_seed(6)
+            .generate(14, 10)
+            .unwrap();
+        let weave = MazeBuilder::from_generator(Box::new(WeaveGenerator {
+            crossing_probability: 0.7,
+        }))
+        .with_seed(6)
+        .generate(14, 10)
+        .unwrap();
+        for maze in [maze, weave] {
+            let shortest = solve_maze(&maze).unwrap().data;
+            for hand in [Hand::Left, Hand::Right] {
+                let trace = WallFollowerSolver { hand }.solve(&maze, &mut rng).unwrap();
+                assert_eq!(trace.path.data, shortest);
+                assert_eq!(trace.steps.first(), Some(&Step::Move(shortest[0])));
+                assert!(trace.steps.len() >= shortest.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use crate::grid::Grid;
+use crate::maze::{Coords, LinkType};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+///Side of a polar cell, a cell can have several outward neighbours once the next ring splits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolarDirection {
+    Inward,
+    Outward(usize),
+    Clockwise,
+    CounterClockwise,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PolarCell {
+    pub inward: LinkType,
+    ///One link per cell of the next ring touching this one, ordered clockwise
+    pub outward: Vec<LinkType>,
+    pub clockwise: LinkType,
+    pub counter_clockwise: LinkType,
+    pub in_maze: bool,
+}
+
+impl PolarCell {
+    pub fn get_link(&self, direction: &PolarDirection) -> &LinkType {
+        match direction {
+            PolarDirection::Inward => &self.inward,
+            PolarDirection::Outward(index) => &self.outward[*index],
+            PolarDirection::Clockwise => &self.clockwise,
+            PolarDirection::CounterClockwise => &self.counter_clockwise,
+        }
+    }
+
+    pub fn set_link(&mut self, direction: &PolarDirection, link_type: LinkType) {
+        match direction {
+            PolarDirection::Inward => self.inward = link_type,
+            PolarDirection::Outward(index) => self.outward[*index] = link_type,
+            PolarDirection::Clockwise => self.clockwise = link_type,
+            PolarDirection::CounterClockwise => self.counter_clockwise = link_type,
+        }
+    }
+}
+
+///Circular maze of concentric rings around a single center cell.
+///Coords are `x`: position in the ring, clockwise from the east, and `y`: ring, 0 being the center.
+///Rings are split into more cells as the radius grows, so cells keep roughly the same width.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PolarMaze {
+    rings: Vec<Vec<PolarCell>>,
+    start_point: Coords,
+    end_point: Coords,
+    seed: u64,
+}
+
+impl PolarMaze {
+    ///Polar grid of `ring_count` rings with every wall standing.
+    ///The maze is entered on the outer ring and solved at the center.
+    pub fn new(ring_count: usize, seed: u64) -> PolarMaze {
+        let ring_count = ring_count.max(1);
+        let mut counts = vec![1];
+        for ring in 1..ring_count {
+            //split cells so they stay about as wide as a ring is high
+            let circumference = 2.0 * PI * ring as f64;
+            let previous = counts[ring - 1];
+            let ratio = ((circumference / previous as f64).round() as usize).max(1);
+            counts.push(previous * ratio);
+        }
+
+        let rings: Vec<Vec<PolarCell>> = (0..ring_count)
+            .map(|ring| {
+                let outward_count = if ring + 1 < ring_count {
+                    counts[ring + 1] / counts[ring]
+                } else {
+                    0
+                };
+                let cell = PolarCell {
+                    inward: LinkType::Wall,
+                    outward: vec![LinkType::Wall; outward_count],
+                    clockwise: LinkType::Wall,
+                    counter_clockwise: LinkType::Wall,
+                    in_maze: false,
+                };
+                vec![cell; counts[ring]]
+            })
+            .collect();
+
+        PolarMaze {
+            rings,
+            start_point: Coords {
+                x: 0,
+                y: ring_count - 1,
+            },
+            end_point: Coords { x: 0, y: 0 },
+            seed,
+        }
+    }
+
+    pub fn borrow_cell(&self, coords: &Coords) -> &PolarCell {
+        &self.rings[coords.y][coords.x]
+    }
+
+    pub fn borrow_cell_mut(&mut self, coords: &Coords) -> &mut PolarCell {
+        &mut self.rings[coords.y][coords.x]
+    }
+
+    pub fn get_ring_count(&self) -> usize {
+        self.rings.len()
+    }
+
+    pub fn get_cell_count(&self, ring: usize) -> usize {
+        self.rings[ring].len()
+    }
+
+    ///Neighbour in `direction`, None if there is no such side
+    pub fn get_neighbour(&self, coords: &Coords, direction: &PolarDirection) -> Option<Coords> {
+        let count = self.get_cell_count(coords.y);
+        match direction {
+            PolarDirection::Inward if coords.y > 0 => {
+                let ratio = count / self.get_cell_count(coords.y - 1);
+                Some(Coords {
+                    x: coords.x / ratio,
+                    y: coords.y - 1,
+                })
+            }
+            PolarDirection::Outward(index) if *index < self.borrow_cell(coords).outward.len() => {
+                let ratio = self.borrow_cell(coords).outward.len();
+                Some(Coords {
+                    x: coords.x * ratio + index,
+                    y: coords.y + 1,
+                })
+            }
+            PolarDirection::Clockwise if count > 1 => Some(Coords {
+                x: (coords.x + 1) % count,
+                y: coords.y,
+            }),
+            PolarDirection::CounterClockwise if count > 1 => Some(Coords {
+                x: (coords.x + count - 1) % count,
+                y: coords.y,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn get_directions_possible(&self, coords: &Coords) -> Vec<PolarDirection> {
+        let mut directions = vec![
+            PolarDirection::Inward,
+            PolarDirection::Clockwise,
+            PolarDirection::CounterClockwise,
+        ];
+        directions.extend((0..self.borrow_cell(coords).outward.len()).map(PolarDirection::Outward));
+        directions.retain(|direction| self.get_neighbour(coords, direction).is_some());
+        directions
+    }
+
+    pub fn direction_to(&self, from: &Coords, to: &Coords) -> Option<PolarDirection> {
+        self.get_directions_possible(from)
+            .into_iter()
+            .find(|direction| self.get_neighbour(from, direction) == Some(*to))
+    }
+
+    ///Side of the neighbour in `direction` that faces back to `coords`
+    fn opposite(&self, coords: &Coords, direction: &PolarDirection) -> PolarDirection {
+        match direction {
+            PolarDirection::Inward => {
+                let ratio = self.get_cell_count(coords.y) / self.get_cell_count(coords.y - 1);
+                PolarDirection::Outward(coords.x % ratio)
+            }
+            PolarDirection::Outward(_) => PolarDirection::Inward,
+            PolarDirection::Clockwise => PolarDirection::CounterClockwise,
+            PolarDirection::CounterClockwise => PolarDirection::Clockwise,
+        }
+    }
+
+    pub fn get_start_point(&self) -> &Coords {
+        &self.start_point
+    }
+
+    pub fn get_end_point(&self) -> &Coords {
+        &self.end_point
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Grid for PolarMaze {
+    type Node = Coords;
+
+    fn nodes(&self) -> Vec<Coords> {
+        self.rings
+            .iter()
+            .enumerate()
+            .flat_map(|(y, ring)| (0..ring.len()).map(move |x| Coords { x, y }))
+            .collect()
+    }
+
+    fn neighbours(&self, coords: &Coords) -> Vec<Coords> {
+        self.get_directions_possible(coords)
+            .iter()
+            .filter_map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn links(&self, coords: &Coords) -> Vec<Coords> {
+        let cell = self.borrow_cell(coords);
+        self.get_directions_possible(coords)
+            .iter()
+            .filter(|direction| cell.get_link(direction) == &LinkType::Path)
+            .filter_map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn link(&mut self, a: &Coords, b: &Coords) {
+        if let Some(direction) = self.direction_to(a, b) {
+            let opposite = self.opposite(a, &direction);
+            self.borrow_cell_mut(a).set_link(&direction, LinkType::Path);
+            self.borrow_cell_mut(b).set_link(&opposite, LinkType::Path);
+        }
+    }
+
+    fn is_in_maze(&self, coords: &Coords) -> bool {
+        self.borrow_cell(coords).in_maze
+    }
+
+    fn set_in_maze(&mut self, coords: &Coords, in_maze: bool) {
+        self.borrow_cell_mut(coords).in_maze = in_maze;
+    }
+
+    fn start(&self) -> Coords {
+        self.start_point
+    }
+
+    fn end(&self) -> Coords {
+        self.end_point
+    }
+}
+
+#[cfg(test)]
+mod tests_polar_maze {
+    use super::PolarMaze;
+    use crate::grid::{generate_with_seed, Grid};
+    use crate::maze::Coords;
+    use crate::maze_generator::wilson::WilsonGenerator;
+    use crate::solver::solve_grid;
+
+    #[test]
+    pub fn test_ring_sizes_grow_with_radius() {
+        let maze = PolarMaze::new(6, 0);
+        let counts: Vec<usize> = (0..6).map(|ring| maze.get_cell_count(ring)).collect();
+        assert_eq!(counts, vec![1, 6, 12, 24, 24, 24]);
+        assert_eq!(maze.neighbours(&Coords { x: 0, y: 0 }).len(), 6);
+    }
+
+    #[test]
+    pub fn test_neighbours_are_symmetric() {
+        let maze = PolarMaze::new(8, 0);
+        for coords in maze.nodes() {
+            for neighbour in maze.neighbours(&coords) {
+                assert!(maze.neighbours(&neighbour).contains(&coords));
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_wilson_generates_perfect_polar_maze() {
+        let mut maze = PolarMaze::new(7, 0);
+        generate_with_seed(&WilsonGenerator, &mut maze, 99).unwrap();
+        let nodes = maze.nodes();
+        let link_count: usize = nodes.iter().map(|c| maze.links(c).len()).sum();
+        assert_eq!(link_count / 2, nodes.len() - 1);
+        let path = solve_grid(&maze, &maze.start(), &maze.end()).unwrap();
+        assert_eq!(path.last(), Some(&Coords { x: 0, y: 0 }));
+    }
+}
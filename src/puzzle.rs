@@ -0,0 +1,171 @@
+use crate::maze::{Coords, Maze};
+use crate::maze_generator::random_index;
+use crate::solver::solve_maze;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+
+///Most locks a maze can have, the solver tracks the collected keys in a bit set
+pub const MAX_LOCKS: usize = 64;
+
+///Locked door on the passage between two cells, opened by the key lying in `key`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lock {
+    pub key: Coords,
+    ///Cells on both sides of the door, `door.0` is the one on the start side
+    pub door: (Coords, Coords),
+}
+
+impl Lock {
+    pub fn blocks(&self, from: &Coords, to: &Coords) -> bool {
+        self.door == (*from, *to) || self.door == (*to, *from)
+    }
+}
+
+///Places up to `count` locked doors on the route to the exit, each with its key.
+///The key of a door is always reachable through the doors before it, so the maze stays solvable.
+///Returns the number of locks placed, limited by the length of the route and [`MAX_LOCKS`].
+pub fn place_locks(maze: &mut Maze, count: usize, rng: &mut dyn RngCore) -> usize {
+    let Ok(path) = solve_maze(maze) else {
+        return 0;
+    };
+    let edge_count = path.data.len().saturating_sub(1);
+    let count = count.min(edge_count).min(MAX_LOCKS);
+
+    //distinct passages of the route, in walking order
+    let mut edges: Vec<usize> = (0..edge_count).collect();
+    let mut picked = Vec::with_capacity(count);
+    for _ in 0..count {
+        picked.push(edges.swap_remove(random_index(rng, edges.len())));
+    }
+    picked.sort_unstable();
+
+    let on_path: HashSet<Coords> = path.data.iter().copied().collect();
+    let mut locks: Vec<Lock> = Vec::with_capacity(count);
+    for (index, edge) in picked.iter().enumerate() {
+        let door = (path.data[*edge], path.data[edge + 1]);
+        //doors from this one on are still shut, the earlier ones can be opened
+        let later: Vec<(Coords, Coords)> = picked[index..]
+            .iter()
+            .map(|edge| (path.data[*edge], path.data[edge + 1]))
+            .collect();
+        let region = reachable_without(maze, &later);
+
+        let free: Vec<Coords> = region
+            .into_iter()
+            .filter(|coords| locks.iter().all(|lock| lock.key != *coords))
+            .collect();
+        //side branches make the player leave the route, the start cell would be no puzzle at all
+        let side_branches: Vec<Coords> = free
+            .iter()
+            .filter(|coords| !on_path.contains(coords))
+            .copied()
+            .collect();
+        let off_start: Vec<Coords> = free
+            .iter()
+            .filter(|coords| **coords != *maze.get_start_point())
+            .copied()
+            .collect();
+        let candidates = [side_branches, off_start, free]
+            .into_iter()
+            .find(|candidates| !candidates.is_empty())
+            .unwrap_or_else(|| vec![*maze.get_start_point()]);
+
+        locks.push(Lock {
+            key: candidates[random_index(rng, candidates.len())],
+            door,
+        });
+    }
+
+    let placed = locks.len();
+    maze.set_locks(locks);
+    placed
+}
+
+///Cells reachable from the start without going through any of the `closed` passages
+fn reachable_without(maze: &Maze, closed: &[(Coords, Coords)]) -> Vec<Coords> {
+    let start = *maze.get_start_point();
+    let mut visited: HashSet<Coords> = HashSet::from([start]);
+    let mut order = vec![start];
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(coords) = queue.pop_front() {
+        for neighbour in maze.get_possible_moves(&coords) {
+            let is_closed = closed
+                .iter()
+                .any(|door| *door == (coords, neighbour) || *door == (neighbour, coords));
+            if is_closed || !visited.insert(neighbour) {
+                continue;
+            }
+            order.push(neighbour);
+            queue.push_back(neighbour);
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests_puzzle {
+    use super::{Lock, MAX_LOCKS};
+    use crate::maze::{Coords, MazeBuilder};
+    use crate::maze_generator::recursive_backtracker::RecursiveBacktrackerGenerator;
+    use crate::solver::{solve_maze, solve_with_keys, SolverError};
+
+    #[test]
+    pub fn test_keys_are_collected_before_their_doors() {
+        let maze = MazeBuilder::from_generator(Box::new(RecursiveBacktrackerGenerator))
+            .with_seed(21)
+            .with_locks(4)
+            .generate(16, 16)
+            .unwrap();
+        let locks: &[Lock] = maze.get_locks();
+        assert_eq!(locks.len(), 4);
+
+        let route = solve_with_keys(&maze).unwrap().data;
+        assert_eq!(route.first(), Some(maze.get_start_point()));
+        assert_eq!(route.last(), Some(maze.get_end_point()));
+        for lock in locks {
+            let key_step = route.iter().position(|coords| *coords == lock.key).unwrap();
+            let door_step = route
+                .windows(2)
+                .position(|pair| lock.blocks(&pair[0], &pair[1]))
+                .unwrap();
+            assert!(key_step <= door_step);
+        }
+        //the plain route ignores the locks and can't be longer
+        assert!(solve_maze(&maze).unwrap().data.len() <= route.len());
+    }
+
+    #[test]
+    pub fn test_locks_are_serialized() {
+        let maze = MazeBuilder::from_generator(Box::new(RecursiveBacktrackerGenerator))
+            .with_seed(2)
+            .with_locks(2)
+            .generate(8, 8)
+            .unwrap();
+        let json = serde_json::to_string(&maze).unwrap();
+        let parsed: crate::maze::Maze = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.get_locks(), maze.get_locks());
+    }
+
+    #[test]
+    pub fn test_too_many_locks_are_rejected() {
+        let mut maze = MazeBuilder::from_generator(Box::new(RecursiveBacktrackerGenerator))
+            .with_seed(3)
+            .generate(10, 10)
+            .unwrap();
+        let key = Coords { x: 0, y: 0 };
+        let locks = vec![
+            Lock {
+                key,
+                door: (key, Coords { x: 1, y: 0 }),
+            };
+            MAX_LOCKS + 1
+        ];
+        maze.set_locks(locks);
+        assert!(matches!(
+            solve_with_keys(&maze),
+            Err(SolverError::TooManyLocks(65))
+        ));
+    }
+}
@@ -0,0 +1,302 @@
+use crate::maze::{Coords, Direction, Maze};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::str::FromStr;
+
+///Rectangular open area of a maze, reached through `doors` openings in its border
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Room {
+    ///Top left cell
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+    ///Number of openings in the border of the room, at least one
+    pub doors: usize,
+}
+
+impl Room {
+    pub fn contains(&self, coords: &Coords) -> bool {
+        coords.x >= self.x
+            && coords.x - self.x < self.width
+            && coords.y >= self.y
+            && coords.y - self.y < self.height
+    }
+
+    pub fn overlaps(&self, other: &Room) -> bool {
+        self.x < other.x.saturating_add(other.width)
+            && other.x < self.x.saturating_add(self.width)
+            && self.y < other.y.saturating_add(other.height)
+            && other.y < self.y.saturating_add(self.height)
+    }
+
+    pub fn cells(&self) -> Vec<Coords> {
+        let (right, bottom) = (
+            self.x.saturating_add(self.width),
+            self.y.saturating_add(self.height),
+        );
+        (self.y..bottom)
+            .flat_map(|y| (self.x..right).map(move |x| Coords { x, y }))
+            .collect()
+    }
+}
+
+///Parses `x,y,width,height` with an optional `,doors`, one door by default
+impl FromStr for Room {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Room, String> {
+        let values = s
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid room value `{value}`"))
+            })
+            .collect::<Result<Vec<usize>, String>>()?;
+        match values[..] {
+            [_, _, width, height] | [_, _, width, height, _] if width == 0 || height == 0 => {
+                Err(format!("Room `{s}` is empty"))
+            }
+            [x, y, width, height] => Ok(Room {
+                x,
+                y,
+                width,
+                height,
+                doors: 1,
+            }),
+            [x, y, width, height, doors] => Ok(Room {
+                x,
+                y,
+                width,
+                height,
+                doors,
+            }),
+            _ => Err(format!(
+                "`{s}` is not in the x,y,width,height[,doors] format"
+            )),
+        }
+    }
+}
+
+///Error if a room leaves the maze, covers masked cells, overlaps another room
+///or has fewer walls to the rest of the maze than doors
+pub fn validate_rooms(maze: &Maze, rooms: &[Room]) -> Result<(), io::Error> {
+    let (width, height) = maze.get_dimensions();
+    for (index, room) in rooms.iter().enumerate() {
+        let invalid = |message: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Room at ({}, {}) of size {}x{} {message}",
+                    room.x, room.y, room.width, room.height
+                ),
+            )
+        };
+        let fits = |start: usize, size: usize, limit: usize| {
+            start.checked_add(size).is_some_and(|end| end <= limit)
+        };
+        if !fits(room.x, room.width, width) || !fits(room.y, room.height, height) {
+            return Err(invalid("does not fit in the maze"));
+        }
+        if room.cells().iter().any(|coords| !maze.is_active(coords)) {
+            return Err(invalid("covers masked cells"));
+        }
+        if rooms[..index].iter().any(|other| room.overlaps(other)) {
+            return Err(invalid("overlaps another room"));
+        }
+        if room.doors == 0 {
+            return Err(invalid("needs at least one door"));
+        }
+        if door_walls(maze, room, rooms).len() < room.doors {
+            return Err(invalid(
+                "has fewer walls to the rest of the maze than doors",
+            ));
+        }
+    }
+    Ok(())
+}
+
+///Takes the rooms out of the maze before generation, like cells outside of a mask,
+///so the generator carves its passages around them. Error if the cells left are not connected.
+pub fn reserve_rooms(maze: &mut Maze, rooms: &[Room]) -> Result<(), io::Error> {
+    for coords in rooms.iter().flat_map(|room| room.cells()) {
+        maze.borrow_cell_mut(&coords).active = false;
+    }
+
+    let cells = maze.get_active_cells();
+    let mut reached: HashSet<Coords> = cells.first().copied().into_iter().collect();
+    let mut queue: VecDeque<Coords> = reached.iter().copied().collect();
+    while let Some(coords) = queue.pop_front() {
+        for direction in maze.get_directions_possible(&coords) {
+            let neighbour = maze.get_neighbour(&coords, &direction);
+            if reached.insert(neighbour) {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    if cells.is_empty() || reached.len() < cells.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Rooms split the rest of the maze into separate parts",
+        ));
+    }
+    Ok(())
+}
+
+///Puts the rooms reserved by [`reserve_rooms`] back into the generated maze, opens them
+///and cuts exactly `doors` openings into the border of each one. Returns the number of doors.
+pub fn open_rooms(maze: &mut Maze, rooms: &[Room], rng: &mut dyn RngCore) -> usize {
+    for coords in rooms.iter().flat_map(|room| room.cells()) {
+        let cell = maze.borrow_cell_mut(&coords);
+        cell.active = true;
+        cell.in_maze = true;
+    }
+    rooms
+        .iter()
+        .map(|room| {
+            for coords in room.cells() {
+                for direction in maze.get_directions_possible(&coords) {
+                    if room.contains(&maze.get_neighbour(&coords, &direction)) {
+                        maze.carve_path(&coords, &direction);
+                    }
+                }
+            }
+            let mut walls = door_walls(maze, room, rooms);
+            walls.shuffle(rng);
+            for (coords, direction) in walls.iter().take(room.doors) {
+                maze.carve_path(coords, direction);
+            }
+            room.doors
+        })
+        .sum()
+}
+
+///Walls between a room and the cells of the maze outside of every room
+fn door_walls(maze: &Maze, room: &Room, rooms: &[Room]) -> Vec<(Coords, Direction)> {
+    let mut walls = Vec::new();
+    for coords in room.cells() {
+        for direction in maze.get_directions_possible(&coords) {
+            let neighbour = maze.get_neighbour(&coords, &direction);
+            if !rooms.iter().any(|other| other.contains(&neighbour)) {
+                walls.push((coords, direction));
+            }
+        }
+    }
+    walls
+}
+
+#[cfg(test)]
+mod tests_room {
+    use super::Room;
+    use crate::grid::Grid;
+    use crate::maze::{Coords, LinkType, MazeBuilder};
+    use crate::maze_generator::{kruskal::KruskalGenerator, weave::WeaveGenerator};
+    use crate::solver::{solve_grid, solve_maze};
+
+    #[test]
+    pub fn test_rooms_are_open_and_connected() {
+        let rooms = [
+            Room {
+                x: 2,
+                y: 2,
+                width: 5,
+                height: 4,
+                doors: 3,
+            },
+            "10,8,4,4".parse::<Room>().unwrap(),
+        ];
+        let mut builder = MazeBuilder::from_generator(Box::new(KruskalGenerator)).with_seed(4);
+        for room in rooms {
+            builder = builder.with_room(room);
+        }
+        let maze = builder.generate(16, 14).unwrap();
+
+        for room in rooms.iter() {
+            for coords in room.cells() {
+                let cell = maze.borrow_cell(&coords);
+                if coords.x + 1 < room.x + room.width {
+                    assert_eq!(cell.right, LinkType::Path);
+                }
+                if coords.y + 1 < room.y + room.height {
+                    assert_eq!(cell.bottom, LinkType::Path);
+                }
+            }
+            let doors = room
+                .cells()
+                .iter()
+                .flat_map(|coords| maze.links(coords))
+                .filter(|neighbour| !room.contains(neighbour))
+                .count();
+            assert_eq!(doors, room.doors);
+        }
+
+        //every cell can still be reached
+        let start = maze.start();
+        let reachable = maze
+            .nodes()
+            .iter()
+            .filter(|coords| solve_grid(&maze, &start, coords).is_ok())
+            .count();
+        assert_eq!(reachable, 16 * 14);
+        assert!(solve_maze(&maze).is_ok());
+    }
+
+    #[test]
+    pub fn test_weave_crossings_stay_out_of_rooms() {
+        let maze = MazeBuilder::from_generator(Box::new(WeaveGenerator {
+            crossing_probability: 1.0,
+        }))
+        .with_seed(2)
+        .with_room("3,3,6,6,2".parse().unwrap())
+        .generate(14, 14)
+        .unwrap();
+        let start = maze.start();
+        for coords in maze.nodes() {
+            assert!(solve_grid(&maze, &start, &coords).is_ok());
+        }
+        assert_eq!(
+            maze.borrow_cell(&Coords { x: 5, y: 5 }).right,
+            LinkType::Path
+        );
+        let room: Room = "3,3,6,6,2".parse().unwrap();
+        let doors = room
+            .cells()
+            .iter()
+            .flat_map(|coords| maze.links(coords))
+            .filter(|neighbour| !room.contains(neighbour))
+            .count();
+        assert_eq!(doors, 2);
+    }
+
+    #[test]
+    pub fn test_invalid_rooms_are_rejected() {
+        assert!("1,2,3".parse::<Room>().is_err());
+        assert!("1,2,0,3".parse::<Room>().is_err());
+        assert!(MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_room("8,0,4,4".parse().unwrap())
+            .generate(10, 10)
+            .is_err());
+        assert!(MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_room("0,0,4,4".parse().unwrap())
+            .with_room("3,3,4,4".parse().unwrap())
+            .generate(10, 10)
+            .is_err());
+        //a room across the whole width cuts the maze in two
+        assert!(MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_room("0,4,10,2,2".parse().unwrap())
+            .generate(10, 10)
+            .is_err());
+        assert!(MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_room("0,0,2,2,5".parse().unwrap())
+            .generate(10, 10)
+            .is_err());
+        assert!(MazeBuilder::from_generator(Box::new(KruskalGenerator))
+            .with_room("18446744073709551615,0,2,2".parse().unwrap())
+            .generate(10, 10)
+            .is_err());
+    }
+}
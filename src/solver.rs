@@ -1,57 +1,750 @@
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
 use thiserror::Error;
 
+use crate::grid::Grid;
 use crate::maze::{Coords, Maze};
+use crate::puzzle::MAX_LOCKS;
 
 #[derive(Error, Debug)]
 pub enum SolverError {
     #[error("End of maze was not reached by solver")]
     EndNotReached,
+    #[error("Cell {0} is not part of the maze")]
+    OutsideMaze(String),
+    #[error("Maze has loops, routes through it are not unique")]
+    NotPerfect,
+    #[error("Maze has {0} locks, the solver can't track the keys of more than {MAX_LOCKS}")]
+    TooManyLocks(usize),
 }
 
+///Route through a maze, `C` is the coordinate type of the maze it belongs to
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Path {
-    pub data: Vec<Coords>,
+pub struct Path<C = Coords> {
+    pub data: Vec<C>,
 }
 
-pub fn solve_maze(maze: &Maze) -> Result<Path, SolverError> {
-    let mut path = Path { data: Vec::new() };
-    let start_coords = maze.get_start_point().clone();
-    let end_coords = maze.get_end_point().clone();
+impl<C> Path<C> {
+    ///Number of moves, one less than the number of cells on the path
+    pub fn length(&self) -> usize {
+        self.data.len().saturating_sub(1)
+    }
+}
 
-    let mut map: HashMap<Coords, Coords> = HashMap::new();
-    let mut queue: VecDeque<Coords> = VecDeque::new();
-    queue.push_back(start_coords);
+///Shortest route from the start to the nearest exit of any maze, [`crate::maze::Maze`],
+///[`crate::maze_3d::Maze3D`] or another [`Grid`]
+pub fn solve_maze<G: Grid>(maze: &G) -> Result<Path<G::Node>, SolverError> {
+    let start = maze.start();
+    let data = maze
+        .ends()
+        .iter()
+        .filter_map(|end| solve_grid(maze, &start, end).ok())
+        .min_by_key(|path| path.len())
+        .ok_or(SolverError::EndNotReached)?;
+    Ok(Path { data })
+}
 
-    while !queue.is_empty() {
-        let coords = queue.pop_front().unwrap();
-        let possible_moves = maze.get_possible_moves(&coords);
-        for possible_move in possible_moves {
-            if map.contains_key(&possible_move) {
+///Error if `node` is outside of the grid or not one of its cells
+fn check_in_grid<G: Grid>(grid: &G, node: &G::Node) -> Result<(), SolverError> {
+    if grid.contains(node) {
+        Ok(())
+    } else {
+        Err(SolverError::OutsideMaze(format!("{node:?}")))
+    }
+}
+
+///Shortest route between any two nodes of a maze, not only its start and end.
+///Error if one of them is not part of the maze.
+pub fn solve_between<G: Grid>(
+    maze: &G,
+    from: &G::Node,
+    to: &G::Node,
+) -> Result<Path<G::Node>, SolverError> {
+    let data = solve_grid(maze, from, to)?;
+    Ok(Path { data })
+}
+
+///Node the furthest away from `start` through the passages, with its distance in moves
+pub fn farthest_node<G: Grid>(grid: &G, start: &G::Node) -> (G::Node, usize) {
+    let mut distances: HashMap<G::Node, usize> = HashMap::from([(*start, 0)]);
+    let mut queue: VecDeque<G::Node> = VecDeque::from([*start]);
+    let mut farthest = (*start, 0);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        if distance > farthest.1 {
+            farthest = (node, distance);
+        }
+        for possible_move in grid.links(&node) {
+            if distances.contains_key(&possible_move) {
                 continue;
             }
-            map.insert(possible_move, coords.clone());
+            distances.insert(possible_move, distance + 1);
             queue.push_back(possible_move);
         }
     }
+    farthest
+}
+
+///Shortest route between two nodes of any [`Grid`], found with a breadth-first search
+pub fn solve_grid<G: Grid>(
+    grid: &G,
+    start: &G::Node,
+    end: &G::Node,
+) -> Result<Vec<G::Node>, SolverError> {
+    search_bfs(grid, start, end).map(|search| search.path.data)
+}
+
+///Route found by one of the searches, with the number of nodes it expanded to find it
+#[derive(Clone, Debug)]
+pub struct Search<C = Coords> {
+    pub path: Path<C>,
+    pub expanded: usize,
+}
+
+///Breadth-first search between two nodes of any [`Grid`], stops as soon as `end` is expanded
+pub fn search_bfs<G: Grid>(
+    grid: &G,
+    start: &G::Node,
+    end: &G::Node,
+) -> Result<Search<G::Node>, SolverError> {
+    check_in_grid(grid, start)?;
+    check_in_grid(grid, end)?;
+    let mut parents: HashMap<G::Node, G::Node> = HashMap::new();
+    let mut queue: VecDeque<G::Node> = VecDeque::from([*start]);
+    let mut expanded = 0;
+
+    while let Some(node) = queue.pop_front() {
+        expanded += 1;
+        if node == *end {
+            let data = retrace(&parents, start, end);
+            return Ok(Search {
+                path: Path { data },
+                expanded,
+            });
+        }
+        for possible_move in grid.links(&node) {
+            if possible_move == *start || parents.contains_key(&possible_move) {
+                continue;
+            }
+            parents.insert(possible_move, node);
+            queue.push_back(possible_move);
+        }
+    }
+    Err(SolverError::EndNotReached)
+}
+
+///Estimate of the distance left to the target, used to order the A* search
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Heuristic {
+    ///Columns plus rows to the target
+    Manhattan,
+    ///Straight line distance to the target
+    Euclidean,
+    ///No estimate, the search becomes Dijkstra's algorithm
+    Zero,
+}
+
+impl Heuristic {
+    pub const ALL: [Heuristic; 3] = [Heuristic::Manhattan, Heuristic::Euclidean, Heuristic::Zero];
+
+    ///Estimate in cells, never more than the real distance in cells
+    pub fn estimate(&self, maze: &Maze, from: &Coords, to: &Coords) -> f64 {
+        let (dx, dy) = maze.get_offset(from, to);
+        match self {
+            Heuristic::Manhattan => (dx + dy) as f64,
+            Heuristic::Euclidean => (dx as f64).hypot(dy as f64),
+            Heuristic::Zero => 0.0,
+        }
+    }
+}
+
+///Entry of the A* open set, the lowest estimate comes out of the heap first
+struct Frontier {
+    estimate: f64,
+    cost: usize,
+    coords: Coords,
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Frontier) -> Ordering {
+        //on a tie the entry the furthest along goes first, it is likely closer to the target
+        other
+            .estimate
+            .total_cmp(&self.estimate)
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Frontier) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Frontier) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Frontier {}
+
+///Most cells a single move can cross, more than 1 only when a tunnel passes under a crossing
+fn longest_move(maze: &Maze) -> usize {
+    maze.nodes()
+        .iter()
+        .flat_map(|coords| {
+            maze.get_possible_moves(coords)
+                .into_iter()
+                .map(move |next| maze.get_offset(coords, &next))
+        })
+        .map(|(dx, dy)| dx + dy)
+        .max()
+        .unwrap_or(1)
+}
+
+///A* search between two cells of a square maze. Every move costs 1 like in [`search_bfs`],
+///the estimate is divided by the longest move so it never overshoots on weave mazes.
+pub fn search_a_star(
+    maze: &Maze,
+    start: &Coords,
+    end: &Coords,
+    heuristic: Heuristic,
+) -> Result<Search, SolverError> {
+    check_in_grid(maze, start)?;
+    check_in_grid(maze, end)?;
+    let reach = longest_move(maze) as f64;
+    let estimate = |coords: &Coords| heuristic.estimate(maze, coords, end) / reach;
+    let mut costs: HashMap<Coords, usize> = HashMap::from([(*start, 0)]);
+    let mut parents: HashMap<Coords, Coords> = HashMap::new();
+    let mut open = BinaryHeap::from([Frontier {
+        estimate: estimate(start),
+        cost: 0,
+        coords: *start,
+    }]);
+    let mut expanded = 0;
+
+    while let Some(Frontier { cost, coords, .. }) = open.pop() {
+        //a cheaper way to this cell was found after this entry was pushed
+        if cost > costs[&coords] {
+            continue;
+        }
+        expanded += 1;
+        if coords == *end {
+            let data = retrace(&parents, start, end);
+            return Ok(Search {
+                path: Path { data },
+                expanded,
+            });
+        }
+        for neighbour in maze.get_possible_moves(&coords) {
+            let next_cost = cost + 1;
+            if costs
+                .get(&neighbour)
+                .is_some_and(|known| *known <= next_cost)
+            {
+                continue;
+            }
+            costs.insert(neighbour, next_cost);
+            parents.insert(neighbour, coords);
+            open.push(Frontier {
+                estimate: next_cost as f64 + estimate(&neighbour),
+                cost: next_cost,
+                coords: neighbour,
+            });
+        }
+    }
+    Err(SolverError::EndNotReached)
+}
+
+///Walks the parent links back from `end`, returns the route from `start`
+fn retrace<N: Copy + Eq + Hash>(parents: &HashMap<N, N>, start: &N, end: &N) -> Vec<N> {
+    let mut path = vec![*end];
+    let mut current = end;
+    while current != start {
+        current = &parents[current];
+        path.push(*current);
+    }
+    path.reverse();
+    path
+}
+
+///Breadth-first tree grown from `root`, answers the shortest route between the root and any
+///node without searching again, e.g. many walkers heading to the same target
+#[derive(Clone, Debug)]
+pub struct ParentTree<N> {
+    root: N,
+    ///Next node towards the root and distance to it in moves
+    parents: HashMap<N, (N, usize)>,
+}
+
+impl<N: Copy + Eq + Hash> ParentTree<N> {
+    ///A root outside of the grid gives an empty tree, no node can reach it
+    pub fn new<G: Grid<Node = N>>(grid: &G, root: &N) -> ParentTree<N> {
+        let mut parents: HashMap<N, (N, usize)> = HashMap::new();
+        let mut queue: VecDeque<N> = VecDeque::new();
+        if grid.contains(root) {
+            parents.insert(*root, (*root, 0));
+            queue.push_back(*root);
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let distance = parents[&node].1;
+            for possible_move in grid.links(&node) {
+                if parents.contains_key(&possible_move) {
+                    continue;
+                }
+                parents.insert(possible_move, (node, distance + 1));
+                queue.push_back(possible_move);
+            }
+        }
+        ParentTree {
+            root: *root,
+            parents,
+        }
+    }
+
+    pub fn get_root(&self) -> &N {
+        &self.root
+    }
+
+    ///Moves between `node` and the root, None if it can't be reached
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.parents.get(node).map(|(_, distance)| *distance)
+    }
+
+    ///Shortest route from `from` to the root
+    pub fn route_to_root(&self, from: &N) -> Result<Path<N>, SolverError> {
+        if !self.parents.contains_key(from) {
+            return Err(SolverError::EndNotReached);
+        }
+        let mut data = vec![*from];
+        let mut current = from;
+        while *current != self.root {
+            current = &self.parents[current].0;
+            data.push(*current);
+        }
+        Ok(Path { data })
+    }
+
+    ///Shortest route from the root to `to`
+    pub fn route_from_root(&self, to: &N) -> Result<Path<N>, SolverError> {
+        let mut path = self.route_to_root(to)?;
+        path.data.reverse();
+        Ok(path)
+    }
+}
+
+///Lowest common ancestor index over a perfect maze. The only route between two nodes goes up
+///the tree from both of them to their common ancestor, found in `O(log n)` without searching.
+#[derive(Clone, Debug)]
+pub struct RouteIndex<N> {
+    indices: HashMap<N, usize>,
+    nodes: Vec<N>,
+    depths: Vec<usize>,
+    ///Separate trees of a maze with unreachable parts never share an ancestor
+    trees: Vec<usize>,
+    ///`ancestors[level][index]` is the ancestor `2^level` moves up, roots are their own ancestor
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl<N: Copy + Eq + Hash> RouteIndex<N> {
+    ///Error if the maze has loops, as the route through the tree would not always be the shortest
+    pub fn new<G: Grid<Node = N>>(grid: &G) -> Result<RouteIndex<N>, SolverError> {
+        let nodes = grid.nodes();
+        let indices: HashMap<N, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (*node, index))
+            .collect();
+        let mut parents: Vec<Option<usize>> = vec![None; nodes.len()];
+        let mut depths = vec![0; nodes.len()];
+        let mut trees = vec![0; nodes.len()];
+        let mut visited = vec![false; nodes.len()];
+        let mut tree_count = 0;
+        let mut link_count = 0;
+
+        for root in 0..nodes.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            trees[root] = tree_count;
+            let mut queue = VecDeque::from([root]);
+            while let Some(index) = queue.pop_front() {
+                for neighbour in grid.links(&nodes[index]) {
+                    link_count += 1;
+                    let next = indices[&neighbour];
+                    if visited[next] {
+                        continue;
+                    }
+                    visited[next] = true;
+                    parents[next] = Some(index);
+                    depths[next] = depths[index] + 1;
+                    trees[next] = tree_count;
+                    queue.push_back(next);
+                }
+            }
+            tree_count += 1;
+        }
+        //every passage is seen from both of its ends
+        if link_count / 2 + tree_count != nodes.len() {
+            return Err(SolverError::NotPerfect);
+        }
+
+        let mut ancestors: Vec<Vec<usize>> = vec![parents
+            .iter()
+            .enumerate()
+            .map(|(index, parent)| parent.unwrap_or(index))
+            .collect()];
+        let max_depth = depths.iter().copied().max().unwrap_or(0);
+        while 1 << ancestors.len() <= max_depth {
+            let last = ancestors.last().unwrap();
+            let level = last.iter().map(|ancestor| last[*ancestor]).collect();
+            ancestors.push(level);
+        }
+
+        Ok(RouteIndex {
+            indices,
+            nodes,
+            depths,
+            trees,
+            ancestors,
+        })
+    }
+
+    ///Moves between two nodes, None if one can't be reached from the other
+    pub fn distance(&self, a: &N, b: &N) -> Option<usize> {
+        let (a, b) = (*self.indices.get(a)?, *self.indices.get(b)?);
+        let ancestor = self.common_ancestor(a, b)?;
+        Some(self.depths[a] + self.depths[b] - 2 * self.depths[ancestor])
+    }
+
+    ///The only route from `from` to `to`
+    pub fn route(&self, from: &N, to: &N) -> Result<Path<N>, SolverError> {
+        let index = |node: &N| {
+            self.indices
+                .get(node)
+                .copied()
+                .ok_or(SolverError::EndNotReached)
+        };
+        let (from, to) = (index(from)?, index(to)?);
+        let ancestor = self
+            .common_ancestor(from, to)
+            .ok_or(SolverError::EndNotReached)?;
+
+        let mut data = self.climb(from, ancestor);
+        data.push(self.nodes[ancestor]);
+        let mut down = self.climb(to, ancestor);
+        down.reverse();
+        data.extend(down);
+        Ok(Path { data })
+    }
+
+    ///Nodes from `index` up to `ancestor`, `ancestor` excluded
+    fn climb(&self, mut index: usize, ancestor: usize) -> Vec<N> {
+        let mut nodes = Vec::with_capacity(self.depths[index] - self.depths[ancestor]);
+        while index != ancestor {
+            nodes.push(self.nodes[index]);
+            index = self.ancestors[0][index];
+        }
+        nodes
+    }
 
-    if !map.contains_key(&end_coords) {
-        return Err(SolverError::EndNotReached);
+    fn lift(&self, mut index: usize, moves: usize) -> usize {
+        for (level, ancestors) in self.ancestors.iter().enumerate() {
+            if moves & (1 << level) != 0 {
+                index = ancestors[index];
+            }
+        }
+        index
     }
 
-    let mut current_coords = &end_coords;
-    while current_coords != &start_coords {
-        path.data.push(current_coords.clone());
-        current_coords = map.get(&current_coords).unwrap();
+    fn common_ancestor(&self, a: usize, b: usize) -> Option<usize> {
+        if self.trees[a] != self.trees[b] {
+            return None;
+        }
+        let (mut a, mut b) = if self.depths[a] >= self.depths[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        a = self.lift(a, self.depths[a] - self.depths[b]);
+        if a == b {
+            return Some(a);
+        }
+        for ancestors in self.ancestors.iter().rev() {
+            if ancestors[a] != ancestors[b] {
+                a = ancestors[a];
+                b = ancestors[b];
+            }
+        }
+        Some(self.ancestors[0][a])
     }
-    path.data.push(start_coords);
-    path.data.reverse();
-    Ok(path)
+}
+
+///Distance in moves of every cell of a square maze from one source cell
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DistanceMap {
+    pub source: Coords,
+    ///Indexed by row then column, None for cells that can't be reached or are masked out
+    pub distances: Vec<Vec<Option<usize>>>,
+    ///Largest distance of the map, the source of a heatmap is 0 and its hottest cell this
+    pub max_distance: usize,
+}
+
+impl DistanceMap {
+    pub fn new(maze: &Maze, source: &Coords) -> DistanceMap {
+        let tree = ParentTree::new(maze, source);
+        let (width, height) = maze.get_dimensions();
+        let distances: Vec<Vec<Option<usize>>> = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| tree.distance(&Coords { x, y }))
+                    .collect()
+            })
+            .collect();
+        let max_distance = distances
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or(0);
+        DistanceMap {
+            source: *source,
+            distances,
+            max_distance,
+        }
+    }
+
+    pub fn get(&self, coords: &Coords) -> Option<usize> {
+        self.distances[coords.y][coords.x]
+    }
+
+    ///Cell the furthest away from the source, one end of the longest route in a perfect maze
+    pub fn farthest(&self) -> (Coords, usize) {
+        self.distances
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(move |(x, distance)| Some((Coords { x, y }, (*distance)?)))
+            })
+            .max_by_key(|(_, distance)| *distance)
+            .unwrap_or((self.source, 0))
+    }
+}
+
+///Up to `limit` distinct routes from `start` to `end` that never visit a node twice,
+///in the order a depth-first search finds them
+pub fn enumerate_paths<G: Grid>(
+    grid: &G,
+    start: &G::Node,
+    end: &G::Node,
+    limit: usize,
+) -> Vec<Path<G::Node>> {
+    let mut paths = Vec::new();
+    walk_simple_paths(grid, start, end, limit, |route| {
+        paths.push(Path {
+            data: route.to_vec(),
+        })
+    });
+    paths
+}
+
+///Number of distinct routes from `start` to `end` that never visit a node twice. The count grows
+///exponentially with the loops of a maze, it stops at `limit` which then means at least `limit`.
+pub fn count_paths<G: Grid>(grid: &G, start: &G::Node, end: &G::Node, limit: usize) -> usize {
+    walk_simple_paths(grid, start, end, limit, |_| {})
+}
+
+///Depth-first search over simple paths, calls `found` with each route and returns how many it found
+fn walk_simple_paths<G: Grid>(
+    grid: &G,
+    start: &G::Node,
+    end: &G::Node,
+    limit: usize,
+    mut found: impl FnMut(&[G::Node]),
+) -> usize {
+    if limit == 0 {
+        return 0;
+    }
+    if start == end {
+        found(&[*start]);
+        return 1;
+    }
+    let pruned = dead_branches(grid, start, end);
+    let mut route = vec![*start];
+    let mut on_route: HashSet<G::Node> = HashSet::from([*start]);
+    let choices = |node: &G::Node, on_route: &HashSet<G::Node>| -> Vec<G::Node> {
+        grid.links(node)
+            .into_iter()
+            .filter(|next| !on_route.contains(next) && !pruned.contains(next))
+            .collect()
+    };
+    let mut stack: Vec<Vec<G::Node>> = vec![choices(start, &on_route)];
+    let mut count = 0;
+
+    while count < limit {
+        let Some(options) = stack.last_mut() else {
+            break;
+        };
+        let Some(next) = options.pop() else {
+            stack.pop();
+            if let Some(node) = route.pop() {
+                on_route.remove(&node);
+            }
+            continue;
+        };
+        route.push(next);
+        if next == *end {
+            found(&route);
+            count += 1;
+            route.pop();
+            continue;
+        }
+        on_route.insert(next);
+        let mut options = choices(&next, &on_route);
+        //at a junction, give up the whole branch if the route cut the end off
+        if options.len() > 1 && !reaches(grid, &next, end, &on_route, &pruned) {
+            options.clear();
+        }
+        stack.push(options);
+    }
+    count
+}
+
+///Dead ends and the corridors leading to them, no simple route between `start` and `end` enters them
+fn dead_branches<G: Grid>(grid: &G, start: &G::Node, end: &G::Node) -> HashSet<G::Node> {
+    let mut open_links: HashMap<G::Node, usize> = grid
+        .nodes()
+        .into_iter()
+        .map(|node| (node, grid.links(&node).len()))
+        .collect();
+    let mut dead_ends: Vec<G::Node> = open_links
+        .iter()
+        .filter(|(node, links)| **links <= 1 && *node != start && *node != end)
+        .map(|(node, _)| *node)
+        .collect();
+    let mut pruned = HashSet::new();
+    while let Some(node) = dead_ends.pop() {
+        if !pruned.insert(node) {
+            continue;
+        }
+        for neighbour in grid.links(&node) {
+            if pruned.contains(&neighbour) || neighbour == *start || neighbour == *end {
+                continue;
+            }
+            let links = open_links.get_mut(&neighbour).unwrap();
+            *links -= 1;
+            if *links <= 1 {
+                dead_ends.push(neighbour);
+            }
+        }
+    }
+    pruned
+}
+
+///True if `end` can be reached from `from` without going through the nodes of `blocked`
+fn reaches<G: Grid>(
+    grid: &G,
+    from: &G::Node,
+    end: &G::Node,
+    blocked: &HashSet<G::Node>,
+    pruned: &HashSet<G::Node>,
+) -> bool {
+    let mut visited: HashSet<G::Node> = HashSet::from([*from]);
+    let mut queue = VecDeque::from([*from]);
+    while let Some(node) = queue.pop_front() {
+        if node == *end {
+            return true;
+        }
+        for neighbour in grid.links(&node) {
+            if blocked.contains(&neighbour) && neighbour != *end
+                || pruned.contains(&neighbour)
+                || !visited.insert(neighbour)
+            {
+                continue;
+            }
+            queue.push_back(neighbour);
+        }
+    }
+    false
+}
+
+///Route from the start to the nearest exit that picks up keys on the way and only
+///goes through a locked door once its key was collected
+pub fn solve_with_keys(maze: &Maze) -> Result<Path, SolverError> {
+    let locks = maze.get_locks();
+    //held keys are the bits of a u64
+    if locks.len() > MAX_LOCKS {
+        return Err(SolverError::TooManyLocks(locks.len()));
+    }
+    let key_bits = |coords: &Coords| -> u64 {
+        locks
+            .iter()
+            .enumerate()
+            .filter(|(_, lock)| lock.key == *coords)
+            .fold(0, |bits, (index, _)| bits | 1 << index)
+    };
+    let exits = maze.get_exits();
+
+    //breadth-first search over (cell, keys held), the shortest route may walk back and forth
+    let start = (*maze.get_start_point(), key_bits(maze.get_start_point()));
+    let mut parents: HashMap<(Coords, u64), (Coords, u64)> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    let mut reached = None;
+
+    while let Some(state) = queue.pop_front() {
+        let (coords, keys) = state;
+        if exits.contains(&coords) {
+            reached = Some(state);
+            break;
+        }
+        for neighbour in maze.get_possible_moves(&coords) {
+            let locked = locks
+                .iter()
+                .enumerate()
+                .any(|(index, lock)| keys & (1 << index) == 0 && lock.blocks(&coords, &neighbour));
+            if locked {
+                continue;
+            }
+            let next = (neighbour, keys | key_bits(&neighbour));
+            if next == start || parents.contains_key(&next) {
+                continue;
+            }
+            parents.insert(next, state);
+            queue.push_back(next);
+        }
+    }
+
+    let mut current = reached.ok_or(SolverError::EndNotReached)?;
+    let mut route = vec![current.0];
+    while current != start {
+        current = parents[&current];
+        route.push(current.0);
+    }
+    route.reverse();
+    Ok(Path { data: route })
 }
 
 #[cfg(test)]
 mod tests_solver {
+    use super::{
+        count_paths, enumerate_paths, farthest_node, search_a_star, search_bfs, solve_between,
+        DistanceMap, Heuristic, ParentTree, RouteIndex, SolverError,
+    };
+    use crate::grid::Grid;
+    use crate::hex_maze::HexMaze;
+    use crate::maze::Coords;
+    use crate::maze::{MazeGenerator, Wrap};
+    use crate::maze_generator::weave::WeaveGenerator;
     use crate::{maze::MazeBuilder, maze_generator::wilson::WilsonGenerator, solver::solve_maze};
 
     #[test]
@@ -62,4 +755,182 @@ mod tests_solver {
             .unwrap();
         println!("{:?}", solve_maze(&maze));
     }
+
+    #[test]
+    pub fn test_a_star_matches_bfs() {
+        let weave = WeaveGenerator {
+            crossing_probability: 1.0,
+        };
+        let mazes = [
+            (
+                Box::new(WilsonGenerator) as Box<dyn MazeGenerator>,
+                Wrap::None,
+            ),
+            (Box::new(WilsonGenerator), Wrap::Torus),
+            (Box::new(weave), Wrap::None),
+        ];
+        for (seed, (generator, wrap)) in mazes.into_iter().enumerate() {
+            let maze = MazeBuilder::from_generator(generator)
+                .with_seed(seed as u64)
+                .with_wrap(wrap)
+                .with_braid(1.0)
+                .generate(30, 30)
+                .unwrap();
+            let (start, end) = (maze.get_start_point(), maze.get_end_point());
+            let bfs = search_bfs(&maze, start, end).unwrap();
+            //without an estimate both searches expand every cell closer than the end and then
+            //some of the cells as far as it, how many of those depends on the tie order
+            let distances = DistanceMap::new(&maze, start);
+            let length = bfs.path.length();
+            let count_within = |limit: usize| {
+                maze.nodes()
+                    .iter()
+                    .filter(|coords| distances.get(coords).is_some_and(|d| d <= limit))
+                    .count()
+            };
+            let (closer, as_far) = (count_within(length - 1), count_within(length));
+            assert!((closer + 1..=as_far).contains(&bfs.expanded));
+            for heuristic in Heuristic::ALL {
+                let search = search_a_star(&maze, start, end, heuristic).unwrap();
+                assert_eq!(search.path.length(), bfs.path.length());
+                assert_eq!(search.path.data.first(), Some(start));
+                assert_eq!(search.path.data.last(), Some(end));
+                for pair in search.path.data.windows(2) {
+                    assert!(maze.get_possible_moves(&pair[0]).contains(&pair[1]));
+                }
+                if heuristic == Heuristic::Zero {
+                    assert!((closer + 1..=as_far).contains(&search.expanded));
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_route_index_matches_search() {
+        let maze = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(5)
+            .generate(15, 12)
+            .unwrap();
+        let index = RouteIndex::new(&maze).unwrap();
+        let target = Coords { x: 7, y: 3 };
+        let tree = ParentTree::new(&maze, &target);
+        let nodes = maze.nodes();
+        for from in nodes.iter().step_by(7) {
+            for to in nodes.iter().step_by(11) {
+                let expected = solve_between(&maze, from, to).unwrap().data;
+                assert_eq!(index.route(from, to).unwrap().data, expected);
+                assert_eq!(index.distance(from, to), Some(expected.len() - 1));
+            }
+            let to_target = tree.route_to_root(from).unwrap().data;
+            assert_eq!(to_target, solve_between(&maze, from, &target).unwrap().data);
+            assert_eq!(tree.distance(from), Some(to_target.len() - 1));
+        }
+    }
+
+    #[test]
+    pub fn test_solve_between_rejects_cells_outside_the_maze() {
+        let maze = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(5)
+            .generate(6, 6)
+            .unwrap();
+        let inside = Coords { x: 2, y: 2 };
+        for outside in [Coords { x: 6, y: 0 }, Coords { x: 0, y: 100 }] {
+            assert!(matches!(
+                solve_between(&maze, &inside, &outside),
+                Err(SolverError::OutsideMaze(_))
+            ));
+            assert!(matches!(
+                solve_between(&maze, &outside, &inside),
+                Err(SolverError::OutsideMaze(_))
+            ));
+            assert!(search_a_star(&maze, &outside, &inside, Heuristic::Zero).is_err());
+            assert_eq!(ParentTree::new(&maze, &outside).distance(&outside), None);
+        }
+    }
+
+    #[test]
+    pub fn test_route_index_needs_a_perfect_maze() {
+        let braided = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(5)
+            .with_braid(1.0)
+            .generate(10, 10)
+            .unwrap();
+        assert!(matches!(
+            RouteIndex::new(&braided),
+            Err(SolverError::NotPerfect)
+        ));
+
+        let mut hex = HexMaze::new(8, 6, 0);
+        crate::grid::generate_with_seed(&WilsonGenerator, &mut hex, 3).unwrap();
+        let index = RouteIndex::new(&hex).unwrap();
+        let (start, end) = (hex.start(), hex.end());
+        assert_eq!(
+            index.route(&start, &end).unwrap().data,
+            solve_between(&hex, &start, &end).unwrap().data
+        );
+    }
+
+    #[test]
+    pub fn test_distance_map() {
+        let maze = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(9)
+            .with_braid(0.5)
+            .generate(12, 9)
+            .unwrap();
+        let source = Coords { x: 4, y: 4 };
+        let map = DistanceMap::new(&maze, &source);
+        assert_eq!(map.distances.len(), 9);
+        assert_eq!(map.get(&source), Some(0));
+        for coords in maze.nodes() {
+            let route = solve_between(&maze, &source, &coords).unwrap();
+            assert_eq!(map.get(&coords), Some(route.data.len() - 1));
+        }
+        let (farthest, distance) = map.farthest();
+        assert_eq!(distance, map.max_distance);
+        assert_eq!(farthest_node(&maze, &source).1, distance);
+        assert_eq!(map.get(&farthest), Some(distance));
+    }
+
+    #[test]
+    pub fn test_enumerate_simple_paths() {
+        let perfect = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(3)
+            .generate(10, 10)
+            .unwrap();
+        let (start, end) = (perfect.start(), perfect.end());
+        let paths = enumerate_paths(&perfect, &start, &end, 10);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].data, solve_maze(&perfect).unwrap().data);
+
+        let braided = MazeBuilder::from_generator(Box::new(WilsonGenerator))
+            .with_seed(3)
+            .with_braid(1.0)
+            .generate(6, 6)
+            .unwrap();
+        let (start, end) = (braided.start(), braided.end());
+        let count = count_paths(&braided, &start, &end, usize::MAX);
+        assert!(count > 1);
+        assert_eq!(count_paths(&braided, &start, &end, 2), 2);
+
+        let paths = enumerate_paths(&braided, &start, &end, usize::MAX);
+        assert_eq!(paths.len(), count);
+        let shortest = solve_maze(&braided).unwrap();
+        assert_eq!(
+            paths.iter().map(|path| path.length()).min(),
+            Some(shortest.length())
+        );
+        let mut distinct: Vec<&Vec<Coords>> = paths.iter().map(|path| &path.data).collect();
+        distinct.sort_by_key(|data| format!("{data:?}"));
+        distinct.dedup();
+        assert_eq!(distinct.len(), count);
+        for path in paths.iter() {
+            assert_eq!(path.data.first(), Some(&start));
+            assert_eq!(path.data.last(), Some(&end));
+            let cells: std::collections::HashSet<&Coords> = path.data.iter().collect();
+            assert_eq!(cells.len(), path.data.len());
+            for pair in path.data.windows(2) {
+                assert!(braided.links(&pair[0]).contains(&pair[1]));
+            }
+        }
+    }
 }
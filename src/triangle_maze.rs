@@ -0,0 +1,287 @@
+use crate::grid::Grid;
+use crate::maze::{Coords, LinkType};
+use serde::{Deserialize, Serialize};
+
+///Sides of a triangle, the third side is the base of an upward triangle
+///and the top of a downward one
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TriangleDirection {
+    Left,
+    Right,
+    Vertical,
+}
+
+impl TriangleDirection {
+    pub const ALL: [TriangleDirection; 3] = [
+        TriangleDirection::Left,
+        TriangleDirection::Right,
+        TriangleDirection::Vertical,
+    ];
+
+    pub fn opposite(&self) -> TriangleDirection {
+        match self {
+            TriangleDirection::Left => TriangleDirection::Right,
+            TriangleDirection::Right => TriangleDirection::Left,
+            TriangleDirection::Vertical => TriangleDirection::Vertical,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TriangleCell {
+    pub left: LinkType,
+    pub right: LinkType,
+    pub vertical: LinkType,
+    pub in_maze: bool,
+}
+
+impl TriangleCell {
+    pub fn get_link(&self, direction: &TriangleDirection) -> &LinkType {
+        match direction {
+            TriangleDirection::Left => &self.left,
+            TriangleDirection::Right => &self.right,
+            TriangleDirection::Vertical => &self.vertical,
+        }
+    }
+
+    pub fn set_link(&mut self, direction: &TriangleDirection, link_type: LinkType) {
+        match direction {
+            TriangleDirection::Left => self.left = link_type,
+            TriangleDirection::Right => self.right = link_type,
+            TriangleDirection::Vertical => self.vertical = link_type,
+        }
+    }
+}
+
+///Maze of alternating upward and downward triangles with three neighbours per cell.
+///The cell at (x, y) points up when x + y is even.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TriangleMaze {
+    data: Vec<Vec<TriangleCell>>,
+    width: usize,
+    height: usize,
+    start_point: Coords,
+    end_point: Coords,
+    seed: u64,
+}
+
+impl TriangleMaze {
+    ///Triangle grid with every wall standing, ready for a [`crate::grid::GridGenerator`]
+    pub fn new(width: usize, height: usize, seed: u64) -> TriangleMaze {
+        let base_cell = TriangleCell {
+            left: LinkType::Wall,
+            right: LinkType::Wall,
+            vertical: LinkType::Wall,
+            in_maze: false,
+        };
+
+        TriangleMaze {
+            data: vec![vec![base_cell; width]; height],
+            width,
+            height,
+            start_point: Coords { x: 0, y: 0 },
+            end_point: Coords {
+                x: width - 1,
+                y: height - 1,
+            },
+            seed,
+        }
+    }
+
+    pub fn borrow_cell(&self, coords: &Coords) -> &TriangleCell {
+        &self.data[coords.y][coords.x]
+    }
+
+    pub fn borrow_cell_mut(&mut self, coords: &Coords) -> &mut TriangleCell {
+        &mut self.data[coords.y][coords.x]
+    }
+
+    pub fn is_upward(coords: &Coords) -> bool {
+        (coords.x + coords.y).is_multiple_of(2)
+    }
+
+    ///Neighbour in `direction`, None at the border of the grid
+    pub fn get_neighbour(&self, coords: &Coords, direction: &TriangleDirection) -> Option<Coords> {
+        match direction {
+            TriangleDirection::Left if coords.x > 0 => Some(Coords {
+                x: coords.x - 1,
+                y: coords.y,
+            }),
+            TriangleDirection::Right if coords.x + 1 < self.width => Some(Coords {
+                x: coords.x + 1,
+                y: coords.y,
+            }),
+            //upward triangles share their base with the row below, downward ones their top with the row above
+            TriangleDirection::Vertical if TriangleMaze::is_upward(coords) => {
+                (coords.y + 1 < self.height).then(|| Coords {
+                    x: coords.x,
+                    y: coords.y + 1,
+                })
+            }
+            TriangleDirection::Vertical if coords.y > 0 => Some(Coords {
+                x: coords.x,
+                y: coords.y - 1,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn get_directions_possible(&self, coords: &Coords) -> Vec<TriangleDirection> {
+        TriangleDirection::ALL
+            .into_iter()
+            .filter(|direction| self.get_neighbour(coords, direction).is_some())
+            .collect()
+    }
+
+    pub fn direction_to(&self, from: &Coords, to: &Coords) -> Option<TriangleDirection> {
+        TriangleDirection::ALL
+            .into_iter()
+            .find(|direction| self.get_neighbour(from, direction) == Some(*to))
+    }
+
+    pub fn get_dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_start_point(&self) -> &Coords {
+        &self.start_point
+    }
+
+    pub fn get_end_point(&self) -> &Coords {
+        &self.end_point
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Grid for TriangleMaze {
+    type Node = Coords;
+
+    fn nodes(&self) -> Vec<Coords> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Coords { x, y }))
+            .collect()
+    }
+
+    fn neighbours(&self, coords: &Coords) -> Vec<Coords> {
+        TriangleDirection::ALL
+            .iter()
+            .filter_map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn links(&self, coords: &Coords) -> Vec<Coords> {
+        let cell = self.borrow_cell(coords);
+        TriangleDirection::ALL
+            .iter()
+            .filter(|direction| cell.get_link(direction) == &LinkType::Path)
+            .filter_map(|direction| self.get_neighbour(coords, direction))
+            .collect()
+    }
+
+    fn link(&mut self, a: &Coords, b: &Coords) {
+        if let Some(direction) = self.direction_to(a, b) {
+            self.borrow_cell_mut(a).set_link(&direction, LinkType::Path);
+            self.borrow_cell_mut(b)
+                .set_link(&direction.opposite(), LinkType::Path);
+        }
+    }
+
+    fn is_in_maze(&self, coords: &Coords) -> bool {
+        self.borrow_cell(coords).in_maze
+    }
+
+    fn set_in_maze(&mut self, coords: &Coords, in_maze: bool) {
+        self.borrow_cell_mut(coords).in_maze = in_maze;
+    }
+
+    fn start(&self) -> Coords {
+        self.start_point
+    }
+
+    fn end(&self) -> Coords {
+        self.end_point
+    }
+}
+
+#[cfg(test)]
+mod tests_triangle_maze {
+    use super::TriangleMaze;
+    use crate::grid::{generate_with_seed, Grid};
+    use crate::maze::Coords;
+    use crate::maze_generator::{
+        growing_tree::{GrowingTreeGenerator, RandomSelector},
+        hunt_and_kill::HuntAndKillGenerator,
+        kruskal::KruskalGenerator,
+        prim::PrimGenerator,
+        recursive_backtracker::RecursiveBacktrackerGenerator,
+    };
+    use crate::solver::solve_grid;
+
+    fn assert_perfect(maze: &TriangleMaze) {
+        let nodes = maze.nodes();
+        let link_count: usize = nodes.iter().map(|c| maze.links(c).len()).sum();
+        assert_eq!(link_count / 2, nodes.len() - 1);
+        let path = solve_grid(maze, &maze.start(), &maze.end()).unwrap();
+        assert_eq!(path.last(), Some(maze.get_end_point()));
+    }
+
+    #[test]
+    pub fn test_neighbours_are_symmetric() {
+        let maze = TriangleMaze::new(9, 6, 0);
+        for coords in maze.nodes() {
+            for direction in maze.get_directions_possible(&coords) {
+                let neighbour = maze.get_neighbour(&coords, &direction).unwrap();
+                assert_eq!(
+                    maze.get_neighbour(&neighbour, &direction.opposite()),
+                    Some(coords)
+                );
+            }
+        }
+        assert_eq!(
+            maze.neighbours(&Coords { x: 2, y: 2 }),
+            vec![
+                Coords { x: 1, y: 2 },
+                Coords { x: 3, y: 2 },
+                Coords { x: 2, y: 3 }
+            ]
+        );
+        assert_eq!(maze.neighbours(&Coords { x: 1, y: 0 }).len(), 2);
+    }
+
+    #[test]
+    pub fn test_generators_build_perfect_triangle_mazes() {
+        let mut maze = TriangleMaze::new(11, 7, 0);
+        generate_with_seed(&RecursiveBacktrackerGenerator, &mut maze, 1).unwrap();
+        assert_perfect(&maze);
+
+        let mut maze = TriangleMaze::new(11, 7, 0);
+        generate_with_seed(&KruskalGenerator, &mut maze, 2).unwrap();
+        assert_perfect(&maze);
+
+        let mut maze = TriangleMaze::new(11, 7, 0);
+        generate_with_seed(&PrimGenerator, &mut maze, 3).unwrap();
+        assert_perfect(&maze);
+
+        let mut maze = TriangleMaze::new(11, 7, 0);
+        generate_with_seed(&HuntAndKillGenerator, &mut maze, 4).unwrap();
+        assert_perfect(&maze);
+
+        let mut maze = TriangleMaze::new(11, 7, 0);
+        let generator = GrowingTreeGenerator {
+            selector: Box::new(RandomSelector),
+        };
+        generate_with_seed(&generator, &mut maze, 5).unwrap();
+        assert_perfect(&maze);
+    }
+}